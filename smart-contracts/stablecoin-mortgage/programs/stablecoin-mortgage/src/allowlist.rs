@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+/// Allowlist settings for a permissioned lending pool
+#[account]
+#[derive(Default)]
+pub struct PoolAccessConfig {
+    /// Lending pool the settings apply to
+    pub lending_pool: Pubkey,
+
+    /// Gatekeeper that issues and revokes attestations for the pool
+    pub gatekeeper: Pubkey,
+
+    /// True if deposits and borrowing require an attestation
+    pub allowlist_enabled: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PoolAccessConfig {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // lending_pool
+                          32 + // gatekeeper
+                          1 + // allowlist_enabled
+                          1; // bump
+}
+
+/// Gatekeeper attestation allowing an address to use a permissioned pool
+#[account]
+#[derive(Default)]
+pub struct PoolAttestation {
+    /// Attested wallet
+    pub subject: Pubkey,
+
+    /// Lending pool the attestation is valid for
+    pub lending_pool: Pubkey,
+
+    /// Gatekeeper that issued the attestation
+    pub gatekeeper: Pubkey,
+
+    /// True if the subject may deposit
+    pub can_deposit: bool,
+
+    /// True if the subject may borrow
+    pub can_borrow: bool,
+
+    /// Issue date
+    pub issued_at: i64,
+
+    /// Expiry date
+    pub expires_at: i64,
+
+    /// True once the gatekeeper has revoked the attestation
+    pub revoked: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PoolAttestation {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // subject
+                          32 + // lending_pool
+                          32 + // gatekeeper
+                          1 + // can_deposit
+                          1 + // can_borrow
+                          8 + // issued_at
+                          8 + // expires_at
+                          1 + // revoked
+                          1; // bump
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PoolAccess {
+    Deposit,
+    Borrow,
+}
+
+/// Enforces the pool allowlist for `subject`.
+/// `pool_access` must be the pool's `["pool_access", pool]` PDA; if it was
+/// never initialized the pool is open to everyone.
+pub fn enforce_pool_access(
+    lending_pool: &Pubkey,
+    pool_access: &AccountInfo,
+    pool_attestation: &AccountInfo,
+    subject: &Pubkey,
+    access: PoolAccess,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"pool_access", lending_pool.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(pool_access.key(), expected, ErrorCode::InvalidParameter);
+    if pool_access.data_is_empty() {
+        return Ok(());
+    }
+    let config = Account::<PoolAccessConfig>::try_from(pool_access)?;
+    if !config.allowlist_enabled {
+        return Ok(());
+    }
+
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"pool_attestation", lending_pool.as_ref(), subject.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(pool_attestation.key(), expected, ErrorCode::NotAllowlisted);
+    require!(!pool_attestation.data_is_empty(), ErrorCode::NotAllowlisted);
+
+    let attestation = Account::<PoolAttestation>::try_from(pool_attestation)?;
+    require!(
+        attestation.gatekeeper == config.gatekeeper && !attestation.revoked,
+        ErrorCode::NotAllowlisted
+    );
+    require!(
+        Clock::get()?.unix_timestamp < attestation.expires_at,
+        ErrorCode::AttestationExpired
+    );
+    let allowed = match access {
+        PoolAccess::Deposit => attestation.can_deposit,
+        PoolAccess::Borrow => attestation.can_borrow,
+    };
+    require!(allowed, ErrorCode::NotAllowlisted);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigurePoolAccess<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PoolAccessConfig::LEN,
+        seeds = [b"pool_access", lending_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_access: Account<'info, PoolAccessConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct IssuePoolAttestation<'info> {
+    #[account(mut)]
+    pub gatekeeper: Signer<'info>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        seeds = [b"pool_access", lending_pool.key().as_ref()],
+        bump = pool_access.bump,
+        constraint = pool_access.gatekeeper == gatekeeper.key() @ ErrorCode::Unauthorized,
+    )]
+    pub pool_access: Account<'info, PoolAccessConfig>,
+
+    /// CHECK: wallet being attested
+    pub subject: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = gatekeeper,
+        space = PoolAttestation::LEN,
+        seeds = [b"pool_attestation", lending_pool.key().as_ref(), subject.key().as_ref()],
+        bump,
+    )]
+    pub pool_attestation: Account<'info, PoolAttestation>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RevokePoolAttestation<'info> {
+    pub gatekeeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool_access", pool_attestation.lending_pool.as_ref()],
+        bump = pool_access.bump,
+        constraint = pool_access.gatekeeper == gatekeeper.key() @ ErrorCode::Unauthorized,
+    )]
+    pub pool_access: Account<'info, PoolAccessConfig>,
+
+    #[account(mut)]
+    pub pool_attestation: Account<'info, PoolAttestation>,
+}
+
+pub fn configure_pool_access(
+    ctx: Context<ConfigurePoolAccess>,
+    gatekeeper: Pubkey,
+    allowlist_enabled: bool,
+) -> Result<()> {
+    let pool_access = &mut ctx.accounts.pool_access;
+    pool_access.lending_pool = ctx.accounts.lending_pool.key();
+    pool_access.gatekeeper = gatekeeper;
+    pool_access.allowlist_enabled = allowlist_enabled;
+    pool_access.bump = ctx.bumps.pool_access;
+    Ok(())
+}
+
+pub fn issue_pool_attestation(
+    ctx: Context<IssuePoolAttestation>,
+    can_deposit: bool,
+    can_borrow: bool,
+    expires_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(expires_at > clock.unix_timestamp, ErrorCode::InvalidParameter);
+
+    let pool_attestation = &mut ctx.accounts.pool_attestation;
+    pool_attestation.subject = ctx.accounts.subject.key();
+    pool_attestation.lending_pool = ctx.accounts.lending_pool.key();
+    pool_attestation.gatekeeper = ctx.accounts.gatekeeper.key();
+    pool_attestation.can_deposit = can_deposit;
+    pool_attestation.can_borrow = can_borrow;
+    pool_attestation.issued_at = clock.unix_timestamp;
+    pool_attestation.expires_at = expires_at;
+    pool_attestation.revoked = false;
+    pool_attestation.bump = ctx.bumps.pool_attestation;
+
+    Ok(())
+}
+
+pub fn revoke_pool_attestation(ctx: Context<RevokePoolAttestation>) -> Result<()> {
+    ctx.accounts.pool_attestation.revoked = true;
+    Ok(())
+}
@@ -0,0 +1,389 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::lien_registry::hand_over_lien;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_mint::{freeze_property_nft, thaw_property_nft};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+
+/// Request for a new borrower to assume an existing mortgage
+#[account]
+#[derive(Default)]
+pub struct AssumptionRequest {
+    /// Mortgage being assumed
+    pub mortgage: Pubkey,
+
+    /// Borrower handing over the mortgage
+    pub current_borrower: Pubkey,
+
+    /// Borrower taking over the mortgage
+    pub new_borrower: Pubkey,
+
+    /// Fee paid to the treasury by the new borrower
+    pub assumption_fee: u64,
+
+    /// Lender or underwriter that approved the assumption
+    pub approved_by: Option<Pubkey>,
+
+    /// New borrower the approval was granted for
+    pub approved_borrower: Pubkey,
+
+    /// Request creation date
+    pub request_date: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AssumptionRequest {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // mortgage
+                          32 + // current_borrower
+                          32 + // new_borrower
+                          8 + // assumption_fee
+                          33 + // Option<Pubkey> approved_by
+                          32 + // approved_borrower
+                          8 + // request_date
+                          1; // bump
+}
+
+#[derive(Accounts)]
+pub struct RequestAssumption<'info> {
+    #[account(mut)]
+    pub current_borrower: Signer<'info>,
+
+    /// CHECK: wallet of the proposed new borrower
+    pub new_borrower: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = mortgage.borrower == current_borrower.key() @ ErrorCode::Unauthorized,
+        constraint = mortgage.is_active @ ErrorCode::MortgageInactive,
+        constraint = !mortgage.is_default @ ErrorCode::MortgageDefaulted,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        init,
+        payer = current_borrower,
+        space = AssumptionRequest::LEN,
+        seeds = [b"assumption", mortgage.key().as_ref()],
+        bump,
+    )]
+    pub assumption_request: Account<'info, AssumptionRequest>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAssumption<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(constraint = mortgage.lending_pool == lending_pool.key() @ ErrorCode::InvalidParameter)]
+    pub mortgage: Account<'info, Mortgage>,
+
+    /// CHECK: wallet of the new borrower being approved
+    pub new_borrower: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"assumption", mortgage.key().as_ref()],
+        bump = assumption_request.bump,
+        constraint = assumption_request.new_borrower == new_borrower.key() @ ErrorCode::InvalidParameter,
+    )]
+    pub assumption_request: Account<'info, AssumptionRequest>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteAssumption<'info> {
+    #[account(mut)]
+    pub current_borrower: Signer<'info>,
+
+    pub new_borrower: Signer<'info>,
+
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        constraint = mortgage.borrower == current_borrower.key() @ ErrorCode::Unauthorized,
+        constraint = mortgage.is_active @ ErrorCode::MortgageInactive,
+        constraint = !mortgage.is_default @ ErrorCode::MortgageDefaulted,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        close = current_borrower,
+        seeds = [b"assumption", mortgage.key().as_ref()],
+        bump = assumption_request.bump,
+        constraint = assumption_request.new_borrower == new_borrower.key() @ ErrorCode::Unauthorized,
+        constraint = assumption_request.approved_borrower == new_borrower.key() @ ErrorCode::AssumptionNotApproved,
+        constraint = assumption_request.approved_by == Some(lending_pool.authority) @ ErrorCode::AssumptionNotApproved,
+    )]
+    pub assumption_request: Account<'info, AssumptionRequest>,
+
+    #[account(constraint = lending_pool.key() == mortgage.lending_pool @ ErrorCode::InvalidParameter)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: new borrower's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: new borrower's KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", current_borrower.key().as_ref()],
+        bump = current_exposure.bump,
+    )]
+    pub current_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", new_borrower.key().as_ref()],
+        bump = new_exposure.bump,
+    )]
+    pub new_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == mortgage.property_nft @ ErrorCode::InvalidParameter,
+        constraint = property_nft.locked_by == Some(mortgage.key()) @ ErrorCode::NFTNotLocked,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    /// CHECK: property lien registry PDA, validated by `hand_over_lien`; uninitialized
+    /// when no liens were ever recorded
+    #[account(mut)]
+    pub lien_registry: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = current_nft_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub current_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = new_nft_account.owner == new_borrower.key() @ ErrorCode::Unauthorized,
+        constraint = new_nft_account.mint == property_nft.mint @ ErrorCode::InvalidParameter,
+    )]
+    pub new_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = new_borrower_token_account.owner == new_borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub new_borrower_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == program_state.treasury @ ErrorCode::InvalidParameter,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAssumption<'info> {
+    #[account(mut)]
+    pub current_borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        close = current_borrower,
+        constraint = assumption_request.current_borrower == current_borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub assumption_request: Account<'info, AssumptionRequest>,
+}
+
+pub fn request_assumption(ctx: Context<RequestAssumption>) -> Result<()> {
+    require_keys_neq!(
+        ctx.accounts.new_borrower.key(),
+        ctx.accounts.current_borrower.key(),
+        ErrorCode::InvalidParameter
+    );
+
+    let assumption_request = &mut ctx.accounts.assumption_request;
+    assumption_request.mortgage = ctx.accounts.mortgage.key();
+    assumption_request.current_borrower = ctx.accounts.current_borrower.key();
+    assumption_request.new_borrower = ctx.accounts.new_borrower.key();
+    assumption_request.assumption_fee = 0;
+    assumption_request.approved_by = None;
+    assumption_request.approved_borrower = Pubkey::default();
+    assumption_request.request_date = Clock::get()?.unix_timestamp;
+    assumption_request.bump = ctx.bumps.assumption_request;
+
+    Ok(())
+}
+
+pub fn approve_assumption(ctx: Context<ApproveAssumption>, assumption_fee: u64) -> Result<()> {
+    let assumption_request = &mut ctx.accounts.assumption_request;
+    assumption_request.assumption_fee = assumption_fee;
+    assumption_request.approved_by = Some(ctx.accounts.authority.key());
+    assumption_request.approved_borrower = ctx.accounts.new_borrower.key();
+    Ok(())
+}
+
+pub fn complete_assumption(ctx: Context<CompleteAssumption>) -> Result<()> {
+    let assumption_fee = ctx.accounts.assumption_request.assumption_fee;
+    let new_borrower = ctx.accounts.new_borrower.key();
+    let mortgage = &ctx.accounts.mortgage;
+
+    // The new borrower is underwritten as if originating the remaining balance
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &new_borrower,
+        PoolAccess::Borrow,
+    )?;
+    let borrower_jurisdiction = enforce_borrower_identity(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &new_borrower,
+    )?;
+    ctx.accounts.jurisdiction_rule.check_loan(
+        LoanProduct::Mortgage,
+        mortgage.interest_rate,
+        mortgage.remaining_balance,
+        ctx.accounts.property_nft.property_value,
+        borrower_jurisdiction,
+    )?;
+    enforce_loan_limits(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.pool_limits,
+        &ctx.accounts.new_exposure,
+        mortgage.remaining_balance,
+        mortgage.remaining_balance,
+    )?;
+
+    if assumption_fee > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.new_borrower_token_account.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.new_borrower.to_account_info(),
+                },
+            ),
+            assumption_fee,
+        )?;
+    }
+
+    // The NFT is thawed only for the move and frozen again under its new owner
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.current_nft_account,
+        &ctx.accounts.current_borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.current_nft_account.to_account_info(),
+                to: ctx.accounts.new_nft_account.to_account_info(),
+                authority: ctx.accounts.current_borrower.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    ctx.accounts.new_nft_account.reload()?;
+    freeze_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.new_nft_account,
+        &ctx.accounts.new_borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    // Rate, balance and schedule carry over unchanged; only the borrower moves
+    let mortgage = &mut ctx.accounts.mortgage;
+    mortgage.borrower = ctx.accounts.new_borrower.key();
+    ctx.accounts.current_exposure.sub(mortgage.remaining_balance);
+    ctx.accounts.new_exposure.add(mortgage.remaining_balance)?;
+
+    // Junior liens are owed by the current owner and block the hand-over; the
+    // mortgage's own lien follows it to the new borrower
+    hand_over_lien(
+        &ctx.accounts.lien_registry,
+        &ctx.accounts.property_nft.key(),
+        &mortgage.key(),
+        Some(&*mortgage),
+        true,
+    )?;
+
+    // The property stays locked to the same mortgage under its new owner
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.owner = ctx.accounts.new_borrower.key();
+    property_nft.token_account = ctx.accounts.new_nft_account.key();
+
+    Ok(())
+}
+
+pub fn cancel_assumption(_ctx: Context<CancelAssumption>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,540 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::pool_limits::BorrowerExposure;
+use crate::property_mint::{freeze_property_nft, thaw_property_nft};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+use crate::tranches::apply_tranche_waterfall;
+
+/// Collateral set for a blanket mortgage: one loan secured by several properties.
+/// The mortgage's `property_nft` points at this account instead of a single NFT.
+#[account]
+#[derive(Default)]
+pub struct BlanketCollateral {
+    /// Mortgage secured by this collateral set
+    pub mortgage: Pubkey,
+
+    /// Borrower who pledged the properties
+    pub borrower: Pubkey,
+
+    /// Sum of the values of all unreleased properties
+    pub aggregate_value: u64,
+
+    /// Pledged properties, in the order they were added
+    pub properties: Vec<CollateralProperty>,
+
+    /// True once liquidation proceeds have been paid into the pool
+    pub proceeds_settled: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct CollateralProperty {
+    /// Property NFT account
+    pub property_nft: Pubkey,
+
+    /// Property value when pledged
+    pub value: u64,
+
+    /// True once the property has been released from the lien
+    pub released: bool,
+
+    /// Share of liquidation proceeds allocated to this property
+    pub liquidation_proceeds: u64,
+}
+
+impl CollateralProperty {
+    pub const LEN: usize = 32 + // property_nft
+                          8 + // value
+                          1 + // released
+                          8; // liquidation_proceeds
+}
+
+impl BlanketCollateral {
+    pub const MAX_PROPERTIES: usize = 10;
+
+    /// Maximum loan-to-value (basis points) allowed after releasing a property
+    pub const MAX_RELEASE_LTV: u64 = 8_000;
+
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // mortgage
+                          32 + // borrower
+                          8 + // aggregate_value
+                          4 + Self::MAX_PROPERTIES * CollateralProperty::LEN + // properties
+                          1 + // proceeds_settled
+                          1; // bump
+
+    fn position(&self, property_nft: &Pubkey) -> Option<usize> {
+        self.properties.iter().position(|p| p.property_nft == *property_nft)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateBlanketCollateral<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = !mortgage.is_active @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = BlanketCollateral::LEN,
+        seeds = [b"blanket_collateral", mortgage.key().as_ref()],
+        bump,
+    )]
+    pub blanket_collateral: Account<'info, BlanketCollateral>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddBlanketProperty<'info> {
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = !mortgage.is_active @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"blanket_collateral", mortgage.key().as_ref()],
+        bump = blanket_collateral.bump,
+    )]
+    pub blanket_collateral: Account<'info, BlanketCollateral>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == borrower.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = !property_nft.is_locked @ ErrorCode::NFTAlreadyLocked,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseBlanketProperty<'info> {
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = !mortgage.is_default @ ErrorCode::MortgageDefaulted,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"blanket_collateral", mortgage.key().as_ref()],
+        bump = blanket_collateral.bump,
+    )]
+    pub blanket_collateral: Account<'info, BlanketCollateral>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(mortgage.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AllocateBlanketProceeds<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = mortgage.lending_pool == lending_pool.key() @ ErrorCode::InvalidParameter,
+        constraint = mortgage.is_default @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"blanket_collateral", mortgage.key().as_ref()],
+        bump = blanket_collateral.bump,
+        constraint = !blanket_collateral.proceeds_settled @ ErrorCode::ProceedsAlreadySettled,
+    )]
+    pub blanket_collateral: Account<'info, BlanketCollateral>,
+
+    /// Account holding the sale proceeds of the seized properties
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Borrower's account receiving any surplus over the outstanding balance
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == mortgage.borrower @ ErrorCode::InvalidParameter,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", mortgage.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Releases a seized property once the liquidation proceeds are settled; the
+/// foreclosure sale itself is recorded by the title registrar
+#[derive(Accounts)]
+pub struct ReleaseSeizedBlanketProperty<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        constraint = mortgage.lending_pool == lending_pool.key() @ ErrorCode::InvalidParameter,
+        constraint = mortgage.is_default @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"blanket_collateral", mortgage.key().as_ref()],
+        bump = blanket_collateral.bump,
+        constraint = blanket_collateral.proceeds_settled @ ErrorCode::InvalidParameter,
+    )]
+    pub blanket_collateral: Account<'info, BlanketCollateral>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(mortgage.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_blanket_collateral(ctx: Context<CreateBlanketCollateral>) -> Result<()> {
+    let blanket_collateral = &mut ctx.accounts.blanket_collateral;
+    let mortgage = &mut ctx.accounts.mortgage;
+
+    blanket_collateral.mortgage = mortgage.key();
+    blanket_collateral.borrower = ctx.accounts.borrower.key();
+    blanket_collateral.aggregate_value = 0;
+    blanket_collateral.properties = Vec::new();
+    blanket_collateral.proceeds_settled = false;
+    blanket_collateral.bump = ctx.bumps.blanket_collateral;
+
+    mortgage.property_nft = blanket_collateral.key();
+    mortgage.property_nft_mint = Pubkey::default();
+    mortgage.property_value = 0;
+
+    Ok(())
+}
+
+pub fn add_blanket_property(ctx: Context<AddBlanketProperty>) -> Result<()> {
+    let blanket_collateral = &mut ctx.accounts.blanket_collateral;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let mortgage = &mut ctx.accounts.mortgage;
+
+    require!(
+        blanket_collateral.properties.len() < BlanketCollateral::MAX_PROPERTIES,
+        ErrorCode::TooManyProperties
+    );
+    require!(
+        blanket_collateral.position(&property_nft.key()).is_none(),
+        ErrorCode::NFTAlreadyLocked
+    );
+
+    blanket_collateral.properties.push(CollateralProperty {
+        property_nft: property_nft.key(),
+        value: property_nft.property_value,
+        released: false,
+        liquidation_proceeds: 0,
+    });
+    blanket_collateral.aggregate_value = blanket_collateral.aggregate_value
+        .checked_add(property_nft.property_value)
+        .ok_or(ErrorCode::Overflow)?;
+
+    property_nft.is_locked = true;
+    property_nft.locked_by = Some(mortgage.key());
+
+    freeze_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &ctx.accounts.borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    mortgage.property_value = blanket_collateral.aggregate_value;
+
+    Ok(())
+}
+
+pub fn release_blanket_property(ctx: Context<ReleaseBlanketProperty>) -> Result<()> {
+    let blanket_collateral = &mut ctx.accounts.blanket_collateral;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let mortgage = &mut ctx.accounts.mortgage;
+
+    let index = blanket_collateral
+        .position(&property_nft.key())
+        .ok_or(ErrorCode::PropertyNotInCollateral)?;
+    require!(
+        !blanket_collateral.properties[index].released,
+        ErrorCode::PropertyAlreadyReleased
+    );
+
+    let remaining_value = blanket_collateral.aggregate_value
+        .checked_sub(blanket_collateral.properties[index].value)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // The loan must stay within the release LTV on the properties left behind
+    if mortgage.remaining_balance > 0 {
+        require!(remaining_value > 0, ErrorCode::ReleaseExceedsLtv);
+        let ltv = (mortgage.remaining_balance as u128)
+            .checked_mul(10_000)
+            .ok_or(ErrorCode::Overflow)?
+            / remaining_value as u128;
+        require!(
+            ltv <= BlanketCollateral::MAX_RELEASE_LTV as u128,
+            ErrorCode::ReleaseExceedsLtv
+        );
+    }
+
+    blanket_collateral.properties[index].released = true;
+    blanket_collateral.aggregate_value = remaining_value;
+
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &ctx.accounts.borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    mortgage.property_value = remaining_value;
+
+    Ok(())
+}
+
+pub fn allocate_blanket_proceeds(
+    ctx: Context<AllocateBlanketProceeds>,
+    proceeds: u64,
+) -> Result<()> {
+    require!(proceeds > 0, ErrorCode::InvalidParameter);
+    let aggregate_value = ctx.accounts.blanket_collateral.aggregate_value;
+    require!(aggregate_value > 0, ErrorCode::InvalidParameter);
+
+    // The pool recovers up to the outstanding balance; the rest goes to the borrower
+    let outstanding = ctx.accounts.mortgage.remaining_balance;
+    let recovered = proceeds.min(outstanding);
+    let surplus = proceeds - recovered;
+
+    if recovered > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.stablecoin_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            recovered,
+        )?;
+    }
+    if surplus > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.borrower_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            surplus,
+        )?;
+    }
+
+    let clock = Clock::get()?;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(outstanding);
+    lending_pool.release_term_liquidity(outstanding, ctx.accounts.mortgage.loan_duration);
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .saturating_sub(outstanding - recovered);
+    lending_pool.last_updated = clock.unix_timestamp;
+    apply_tranche_waterfall(
+        &lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        0,
+        outstanding - recovered,
+    )?;
+    ctx.accounts.borrower_exposure.sub(outstanding);
+
+    let mortgage = &mut ctx.accounts.mortgage;
+    mortgage.remaining_balance = 0;
+    mortgage.is_active = false;
+    mortgage.close_date = Some(clock.unix_timestamp);
+
+    let blanket_collateral = &mut ctx.accounts.blanket_collateral;
+    blanket_collateral.proceeds_settled = true;
+
+    // Pro rata by pledged value; rounding dust goes to the last unreleased property
+    let mut allocated: u64 = 0;
+    let mut last_index = None;
+    for (index, property) in blanket_collateral.properties.iter_mut().enumerate() {
+        if property.released {
+            continue;
+        }
+        let share = (proceeds as u128)
+            .checked_mul(property.value as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / aggregate_value as u128;
+        property.liquidation_proceeds = u64::try_from(share).map_err(|_| ErrorCode::Overflow)?;
+        allocated = allocated
+            .checked_add(property.liquidation_proceeds)
+            .ok_or(ErrorCode::Overflow)?;
+        last_index = Some(index);
+    }
+
+    if let Some(index) = last_index {
+        let dust = proceeds.checked_sub(allocated).ok_or(ErrorCode::Overflow)?;
+        let last = &mut blanket_collateral.properties[index];
+        last.liquidation_proceeds = last.liquidation_proceeds
+            .checked_add(dust)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    Ok(())
+}
+
+pub fn release_seized_blanket_property(ctx: Context<ReleaseSeizedBlanketProperty>) -> Result<()> {
+    let blanket_collateral = &mut ctx.accounts.blanket_collateral;
+    let property_nft = &mut ctx.accounts.property_nft;
+
+    let index = blanket_collateral
+        .position(&property_nft.key())
+        .ok_or(ErrorCode::PropertyNotInCollateral)?;
+    require!(
+        !blanket_collateral.properties[index].released,
+        ErrorCode::PropertyAlreadyReleased
+    );
+    blanket_collateral.properties[index].released = true;
+
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &property_nft.owner,
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )
+}
@@ -0,0 +1,738 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::lien_registry::hand_over_lien;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_mint::{freeze_property_nft, thaw_property_nft};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+
+/// Escrow for selling a mortgaged property in a single settlement transaction
+#[account]
+#[derive(Default)]
+pub struct ClosingEscrow {
+    /// Seller (current property owner and borrower)
+    pub seller: Pubkey,
+
+    /// Buyer of the property
+    pub buyer: Pubkey,
+
+    /// Property NFT being sold
+    pub property_nft: Pubkey,
+
+    /// Seller's mortgage paid off at settlement
+    pub seller_mortgage: Pubkey,
+
+    /// Agreed purchase price
+    pub purchase_price: u64,
+
+    /// Part of the price financed by a new mortgage for the buyer (0 for cash sales)
+    pub buyer_loan_amount: u64,
+
+    /// Lending pool financing the buyer, if any
+    pub buyer_lending_pool: Option<Pubkey>,
+
+    /// Token account holding the buyer's deposit
+    pub escrow_vault: Pubkey,
+
+    /// True once the buyer has deposited their share of the price
+    pub is_funded: bool,
+
+    /// Authority of the buyer's lending pool once it has approved the financing
+    pub financing_approved_by: Option<Pubkey>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ClosingEscrow {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // seller
+                          32 + // buyer
+                          32 + // property_nft
+                          32 + // seller_mortgage
+                          8 + // purchase_price
+                          8 + // buyer_loan_amount
+                          33 + // Option<Pubkey> buyer_lending_pool
+                          32 + // escrow_vault
+                          1 + // is_funded
+                          33 + // Option<Pubkey> financing_approved_by
+                          1; // bump
+
+    /// Amount the buyer deposits up front
+    pub fn buyer_deposit(&self) -> u64 {
+        self.purchase_price.saturating_sub(self.buyer_loan_amount)
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenClosingEscrow<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: buyer wallet recorded in the escrow
+    pub buyer: UncheckedAccount<'info>,
+
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == seller.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = property_nft.locked_by == Some(seller_mortgage.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        constraint = seller_mortgage.borrower == seller.key() @ ErrorCode::Unauthorized,
+        constraint = seller_mortgage.is_active @ ErrorCode::MortgageInactive,
+        constraint = !seller_mortgage.is_default @ ErrorCode::MortgageDefaulted,
+    )]
+    pub seller_mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        constraint = stablecoin_mint.key() == program_state.stablecoin_mint @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = ClosingEscrow::LEN,
+        seeds = [b"closing_escrow", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub closing_escrow: Account<'info, ClosingEscrow>,
+
+    #[account(
+        init,
+        payer = seller,
+        token::mint = stablecoin_mint,
+        token::authority = closing_escrow,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DepositPurchasePrice<'info> {
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"closing_escrow", closing_escrow.property_nft.as_ref()],
+        bump = closing_escrow.bump,
+        constraint = closing_escrow.buyer == buyer.key() @ ErrorCode::Unauthorized,
+        constraint = !closing_escrow.is_funded @ ErrorCode::EscrowAlreadyFunded,
+    )]
+    pub closing_escrow: Account<'info, ClosingEscrow>,
+
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == closing_escrow.escrow_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveClosingFinancing<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"closing_escrow", closing_escrow.property_nft.as_ref()],
+        bump = closing_escrow.bump,
+        constraint = closing_escrow.buyer_lending_pool == Some(lending_pool.key()) @ ErrorCode::InvalidParameter,
+    )]
+    pub closing_escrow: Account<'info, ClosingEscrow>,
+}
+
+#[derive(Accounts)]
+pub struct SettleClosing<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"closing_escrow", property_nft.key().as_ref()],
+        bump = closing_escrow.bump,
+        constraint = closing_escrow.buyer == buyer.key() @ ErrorCode::Unauthorized,
+        constraint = closing_escrow.seller == seller.key() @ ErrorCode::Unauthorized,
+        constraint = closing_escrow.is_funded @ ErrorCode::EscrowNotFunded,
+    )]
+    pub closing_escrow: Account<'info, ClosingEscrow>,
+
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == closing_escrow.escrow_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(seller_mortgage.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    /// Linked parcel record; required when the escrow has a buyer loan
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Option<Account<'info, PropertyRecord>>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_mortgage.key() == closing_escrow.seller_mortgage @ ErrorCode::InvalidParameter,
+        constraint = seller_mortgage.is_active @ ErrorCode::MortgageInactive,
+    )]
+    pub seller_mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        constraint = seller_lending_pool.key() == seller_mortgage.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub seller_lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        constraint = seller_pool_vault.key() == seller_lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub seller_pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", seller.key().as_ref()],
+        bump = seller_exposure.bump,
+    )]
+    pub seller_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == seller.key() @ ErrorCode::Unauthorized,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = seller_nft_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub seller_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = buyer_nft_account.owner == buyer.key() @ ErrorCode::Unauthorized,
+        constraint = buyer_nft_account.mint == property_nft.mint @ ErrorCode::InvalidParameter,
+    )]
+    pub buyer_nft_account: Account<'info, TokenAccount>,
+
+    /// Pool financing the buyer; required when the escrow has a buyer loan
+    #[account(mut)]
+    pub buyer_lending_pool: Option<Account<'info, LendingPool>>,
+
+    /// Vault of the pool financing the buyer
+    #[account(mut)]
+    pub buyer_pool_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: buyer pool allowlist config PDA, validated by `enforce_pool_access`;
+    /// uninitialized for open pools
+    pub buyer_pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: buyer's attestation for the buyer pool, validated by `enforce_pool_access`
+    pub buyer_pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: buyer pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub buyer_pool_limits: UncheckedAccount<'info>,
+
+    /// Buyer's exposure across all pools; required when the escrow has a buyer loan
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", buyer.key().as_ref()],
+        bump = buyer_exposure.bump,
+    )]
+    pub buyer_exposure: Option<Account<'info, BorrowerExposure>>,
+
+    /// Buyer's new mortgage, originated during settlement
+    #[account(init, payer = buyer, space = Mortgage::LEN)]
+    pub buyer_mortgage: Option<Account<'info, Mortgage>>,
+
+    /// CHECK: buyer KYC attestation, validated by `enforce_borrower_identity`
+    pub buyer_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the buyer identity, validated by `enforce_borrower_identity`
+    pub buyer_identity_provider: UncheckedAccount<'info>,
+
+    /// Rules for the property's jurisdiction; required when the escrow has a buyer loan
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Option<Account<'info, JurisdictionRule>>,
+
+    /// Title attestation; the registrar must have recorded the transfer to the buyer
+    /// before settlement
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.records_owner(&closing_escrow.buyer, &title_registrar)
+            @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    /// CHECK: property lien registry PDA, validated by `hand_over_lien`; uninitialized
+    /// when no liens were ever recorded
+    #[account(mut)]
+    pub lien_registry: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CancelClosingEscrow<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: seller receives the escrow rent back
+    #[account(mut, constraint = seller.key() == closing_escrow.seller @ ErrorCode::Unauthorized)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"closing_escrow", closing_escrow.property_nft.as_ref()],
+        bump = closing_escrow.bump,
+        constraint = signer.key() == closing_escrow.seller
+            || signer.key() == closing_escrow.buyer @ ErrorCode::Unauthorized,
+    )]
+    pub closing_escrow: Account<'info, ClosingEscrow>,
+
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == closing_escrow.escrow_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == closing_escrow.buyer @ ErrorCode::Unauthorized,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn open_closing_escrow(
+    ctx: Context<OpenClosingEscrow>,
+    purchase_price: u64,
+    buyer_loan_amount: u64,
+    buyer_lending_pool: Option<Pubkey>,
+) -> Result<()> {
+    let seller_mortgage = &ctx.accounts.seller_mortgage;
+
+    require!(
+        purchase_price >= seller_mortgage.remaining_balance,
+        ErrorCode::PurchasePriceBelowPayoff
+    );
+    require!(buyer_loan_amount < purchase_price, ErrorCode::InvalidLoanAmount);
+    require!(
+        buyer_loan_amount == 0 || buyer_lending_pool.is_some(),
+        ErrorCode::InvalidParameter
+    );
+
+    let closing_escrow = &mut ctx.accounts.closing_escrow;
+    closing_escrow.seller = ctx.accounts.seller.key();
+    closing_escrow.buyer = ctx.accounts.buyer.key();
+    closing_escrow.property_nft = ctx.accounts.property_nft.key();
+    closing_escrow.seller_mortgage = seller_mortgage.key();
+    closing_escrow.purchase_price = purchase_price;
+    closing_escrow.buyer_loan_amount = buyer_loan_amount;
+    closing_escrow.buyer_lending_pool = if buyer_loan_amount > 0 { buyer_lending_pool } else { None };
+    closing_escrow.escrow_vault = ctx.accounts.escrow_vault.key();
+    closing_escrow.is_funded = false;
+    closing_escrow.financing_approved_by = None;
+    closing_escrow.bump = ctx.bumps.closing_escrow;
+
+    Ok(())
+}
+
+pub fn approve_closing_financing(ctx: Context<ApproveClosingFinancing>) -> Result<()> {
+    ctx.accounts.closing_escrow.financing_approved_by = Some(ctx.accounts.authority.key());
+    Ok(())
+}
+
+pub fn deposit_purchase_price(ctx: Context<DepositPurchasePrice>) -> Result<()> {
+    let closing_escrow = &mut ctx.accounts.closing_escrow;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.buyer_token_account.to_account_info(),
+                to: ctx.accounts.escrow_vault.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        closing_escrow.buyer_deposit(),
+    )?;
+
+    closing_escrow.is_funded = true;
+
+    Ok(())
+}
+
+pub fn settle_closing(ctx: Context<SettleClosing>) -> Result<()> {
+    let clock = Clock::get()?;
+    let closing_escrow = &ctx.accounts.closing_escrow;
+    let payoff = ctx.accounts.seller_mortgage.remaining_balance;
+    // A buyer financed by the seller's pool loads that pool twice; both legs are then
+    // booked on the buyer copy and mirrored so neither write clobbers the other
+    let same_pool = ctx.accounts.buyer_lending_pool
+        .as_ref()
+        .map_or(false, |pool| pool.key() == ctx.accounts.seller_lending_pool.key());
+
+    require!(
+        closing_escrow.purchase_price >= payoff,
+        ErrorCode::PurchasePriceBelowPayoff
+    );
+
+    // 1. Fund the buyer's loan into escrow and originate their mortgage
+    if closing_escrow.buyer_loan_amount > 0 {
+        let program_state = &ctx.accounts.program_state;
+        let loan_amount = closing_escrow.buyer_loan_amount;
+        let buyer_lending_pool = ctx.accounts.buyer_lending_pool
+            .as_mut()
+            .ok_or(ErrorCode::InvalidParameter)?;
+        let buyer_pool_vault = ctx.accounts.buyer_pool_vault
+            .as_ref()
+            .ok_or(ErrorCode::InvalidParameter)?;
+        let buyer_mortgage = ctx.accounts.buyer_mortgage
+            .as_mut()
+            .ok_or(ErrorCode::InvalidParameter)?;
+
+        require!(
+            Some(buyer_lending_pool.key()) == closing_escrow.buyer_lending_pool,
+            ErrorCode::InvalidParameter
+        );
+        require!(
+            buyer_pool_vault.key() == buyer_lending_pool.stablecoin_vault,
+            ErrorCode::InvalidParameter
+        );
+        require!(buyer_lending_pool.is_active, ErrorCode::PoolInactive);
+        require!(
+            closing_escrow.financing_approved_by == Some(buyer_lending_pool.authority),
+            ErrorCode::FinancingNotApproved
+        );
+        enforce_pool_access(
+            &buyer_lending_pool.key(),
+            &ctx.accounts.buyer_pool_access,
+            &ctx.accounts.buyer_pool_attestation,
+            &closing_escrow.buyer,
+            PoolAccess::Borrow,
+        )?;
+        let borrower_jurisdiction = enforce_borrower_identity(
+            buyer_lending_pool,
+            &ctx.accounts.buyer_identity,
+            &ctx.accounts.buyer_identity_provider,
+            &closing_escrow.buyer,
+        )?;
+        require!(
+            loan_amount >= program_state.min_loan_amount
+                && loan_amount <= program_state.max_loan_amount,
+            ErrorCode::InvalidLoanAmount
+        );
+        require!(
+            buyer_lending_pool.interest_rate >= program_state.min_interest_rate
+                && buyer_lending_pool.interest_rate <= program_state.max_interest_rate,
+            ErrorCode::InvalidInterestRate
+        );
+        require!(
+            ctx.accounts.property_nft.value_current,
+            ErrorCode::InvalidAssessment
+        );
+        require!(
+            ctx.accounts.property_record.is_some(),
+            ErrorCode::PropertyRecordMissing
+        );
+        // Lend against the lower of the agreed price and the assessed value
+        let collateral_value = closing_escrow.purchase_price
+            .min(ctx.accounts.property_nft.property_value);
+        ctx.accounts.jurisdiction_rule
+            .as_ref()
+            .ok_or(ErrorCode::InvalidParameter)?
+            .check_loan(
+                LoanProduct::Mortgage,
+                buyer_lending_pool.interest_rate,
+                loan_amount,
+                collateral_value,
+                borrower_jurisdiction,
+            )?;
+        require!(
+            buyer_lending_pool.available_liquidity() >= loan_amount,
+            ErrorCode::InsufficientLiquidity
+        );
+        let buyer_exposure = ctx.accounts.buyer_exposure
+            .as_mut()
+            .ok_or(ErrorCode::InvalidParameter)?;
+        enforce_loan_limits(
+            buyer_lending_pool,
+            &ctx.accounts.buyer_pool_limits,
+            buyer_exposure,
+            loan_amount,
+            loan_amount,
+        )?;
+        buyer_exposure.add(loan_amount)?;
+        let loan_duration = buyer_lending_pool.loan_duration;
+        buyer_lending_pool.match_term_liquidity(loan_amount, loan_duration)?;
+
+        let seeds = &[
+            b"lending_pool".as_ref(),
+            buyer_lending_pool.authority.as_ref(),
+            &[buyer_lending_pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: buyer_pool_vault.to_account_info(),
+                    to: ctx.accounts.escrow_vault.to_account_info(),
+                    authority: buyer_lending_pool.to_account_info(),
+                },
+                signer,
+            ),
+            loan_amount,
+        )?;
+
+        buyer_lending_pool.total_borrowed = buyer_lending_pool.total_borrowed
+            .checked_add(loan_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        buyer_lending_pool.last_updated = clock.unix_timestamp;
+
+        let months = buyer_lending_pool.loan_duration / Mortgage::PAYMENT_INTERVAL as u64;
+        buyer_mortgage.borrower = closing_escrow.buyer;
+        buyer_mortgage.lending_pool = buyer_lending_pool.key();
+        buyer_mortgage.property_nft = ctx.accounts.property_nft.key();
+        buyer_mortgage.property_nft_mint = ctx.accounts.property_nft.mint;
+        buyer_mortgage.loan_amount = loan_amount;
+        buyer_mortgage.property_value = collateral_value;
+        buyer_mortgage.loan_duration = buyer_lending_pool.loan_duration;
+        buyer_mortgage.interest_rate = buyer_lending_pool.interest_rate;
+        buyer_mortgage.monthly_payment =
+            Mortgage::amortized_payment(loan_amount, buyer_lending_pool.interest_rate, months)?;
+        buyer_mortgage.remaining_balance = loan_amount;
+        buyer_mortgage.next_payment_due = clock.unix_timestamp + Mortgage::PAYMENT_INTERVAL;
+        buyer_mortgage.payments_made = 0;
+        buyer_mortgage.is_active = true;
+        buyer_mortgage.is_default = false;
+        buyer_mortgage.funding_date = clock.unix_timestamp;
+        buyer_mortgage.close_date = None;
+    }
+
+    let escrow_seeds = &[
+        b"closing_escrow".as_ref(),
+        closing_escrow.property_nft.as_ref(),
+        &[closing_escrow.bump],
+    ];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    // 2. Pay off the seller's mortgage
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: ctx.accounts.seller_pool_vault.to_account_info(),
+                authority: closing_escrow.to_account_info(),
+            },
+            escrow_signer,
+        ),
+        payoff,
+    )?;
+
+    let seller_mortgage = &mut ctx.accounts.seller_mortgage;
+    seller_mortgage.remaining_balance = 0;
+    seller_mortgage.is_active = false;
+    seller_mortgage.close_date = Some(clock.unix_timestamp);
+
+    let seller_lending_pool: &mut LendingPool = match ctx.accounts.buyer_lending_pool.as_mut() {
+        Some(buyer_lending_pool) if same_pool => buyer_lending_pool,
+        _ => &mut ctx.accounts.seller_lending_pool,
+    };
+    seller_lending_pool.total_borrowed = seller_lending_pool.total_borrowed.saturating_sub(payoff);
+    seller_lending_pool.release_term_liquidity(payoff, seller_mortgage.loan_duration);
+    seller_lending_pool.last_updated = clock.unix_timestamp;
+    if same_pool {
+        let pool = seller_lending_pool.clone();
+        ctx.accounts.seller_lending_pool.set_inner(pool);
+    }
+    ctx.accounts.seller_exposure.sub(payoff);
+
+    // The seller's lien goes with the payoff; the buyer's loan takes its place
+    let buyer_mortgage = ctx.accounts.buyer_mortgage
+        .as_ref()
+        .filter(|_| closing_escrow.buyer_loan_amount > 0);
+    hand_over_lien(
+        &ctx.accounts.lien_registry,
+        &ctx.accounts.property_nft.key(),
+        &ctx.accounts.seller_mortgage.key(),
+        buyer_mortgage,
+        true,
+    )?;
+
+    // 3. Send the net proceeds to the seller
+    let net_proceeds = closing_escrow.purchase_price - payoff;
+    if net_proceeds > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.escrow_vault.to_account_info(),
+                    to: ctx.accounts.seller_token_account.to_account_info(),
+                    authority: closing_escrow.to_account_info(),
+                },
+                escrow_signer,
+            ),
+            net_proceeds,
+        )?;
+    }
+
+    // The vault is empty once the price has been paid out; return its rent to the seller
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.escrow_vault.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: closing_escrow.to_account_info(),
+        },
+        escrow_signer,
+    ))?;
+
+    // 4. Move the property NFT to the buyer and release the seller's lien
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.seller_nft_account,
+        &ctx.accounts.seller.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.seller_nft_account.to_account_info(),
+                to: ctx.accounts.buyer_nft_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    ctx.accounts.buyer_nft_account.reload()?;
+
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.owner = closing_escrow.buyer;
+    property_nft.token_account = ctx.accounts.buyer_nft_account.key();
+    match ctx.accounts.buyer_mortgage.as_ref() {
+        Some(buyer_mortgage) if closing_escrow.buyer_loan_amount > 0 => {
+            property_nft.is_locked = true;
+            property_nft.locked_by = Some(buyer_mortgage.key());
+            freeze_property_nft(
+                &ctx.accounts.property_mint,
+                &ctx.accounts.buyer_nft_account,
+                &ctx.accounts.buyer.key(),
+                &ctx.accounts.property_mint_authority,
+                ctx.bumps.property_mint_authority,
+                &ctx.accounts.token_program,
+            )?;
+        }
+        _ => {
+            property_nft.is_locked = false;
+            property_nft.locked_by = None;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn cancel_closing_escrow(ctx: Context<CancelClosingEscrow>) -> Result<()> {
+    let closing_escrow = &ctx.accounts.closing_escrow;
+    let seeds = &[
+        b"closing_escrow".as_ref(),
+        closing_escrow.property_nft.as_ref(),
+        &[closing_escrow.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if ctx.accounts.escrow_vault.amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.escrow_vault.to_account_info(),
+                    to: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: closing_escrow.to_account_info(),
+                },
+                signer,
+            ),
+            ctx.accounts.escrow_vault.amount,
+        )?;
+    }
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.escrow_vault.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: closing_escrow.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ErrorCode;
+use crate::governance::{Proposal, ProposalStatus};
+use crate::state::*;
+
+/// Proposal parameter key for jurisdiction rule changes
+pub const JURISDICTION_RULE_PROPOSAL_KEY: &str = "jurisdiction_rule";
+
+/// Loan products that a jurisdiction can permit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LoanProduct {
+    Mortgage,
+    Heloc,
+    Construction,
+    Refinance,
+    Fractional,
+}
+
+impl LoanProduct {
+    /// Bit for this product in `JurisdictionRule::allowed_products`
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Lending rules for a jurisdiction, set by governance proposal
+#[account]
+#[derive(Default)]
+pub struct JurisdictionRule {
+    /// Jurisdiction the rule applies to
+    pub jurisdiction: JurisdictionCode,
+
+    /// Usury cap on the loan interest rate (basis points)
+    pub max_interest_rate: u64,
+
+    /// Maximum loan-to-value at origination (basis points)
+    pub max_ltv: u64,
+
+    /// Hash of the disclosures borrowers must receive in this jurisdiction
+    pub disclosures_hash: [u8; 32],
+
+    /// Bitmask of permitted `LoanProduct`s
+    pub allowed_products: u8,
+
+    /// Last update date
+    pub updated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl JurisdictionRule {
+    pub const LEN: usize = 8 + // discriminator
+                          8 + // jurisdiction
+                          8 + // max_interest_rate
+                          8 + // max_ltv
+                          32 + // disclosures_hash
+                          1 + // allowed_products
+                          8 + // updated_at
+                          1; // bump
+
+    /// Value a rule-change proposal must carry as `new_value`: the first 8 bytes of
+    /// the hash of the proposed rule, so voters approve one exact rule
+    pub fn proposal_value(
+        jurisdiction: &JurisdictionCode,
+        max_interest_rate: u64,
+        max_ltv: u64,
+        disclosures_hash: &[u8; 32],
+        allowed_products: u8,
+    ) -> u64 {
+        let hash = hashv(&[
+            jurisdiction.as_ref(),
+            &max_interest_rate.to_le_bytes(),
+            &max_ltv.to_le_bytes(),
+            disclosures_hash.as_ref(),
+            &[allowed_products],
+        ]);
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&hash.to_bytes()[..8]);
+        u64::from_le_bytes(value)
+    }
+
+    /// Checks a new loan against the jurisdiction's product, usury and LTV rules.
+    /// `borrower_jurisdiction` is where the borrower was KYC-verified, if the pool
+    /// requires verification; it must be this jurisdiction.
+    pub fn check_loan(
+        &self,
+        product: LoanProduct,
+        interest_rate: u64,
+        loan_amount: u64,
+        property_value: u64,
+        borrower_jurisdiction: Option<JurisdictionCode>,
+    ) -> Result<()> {
+        if let Some(borrower_jurisdiction) = borrower_jurisdiction {
+            require!(
+                borrower_jurisdiction == self.jurisdiction,
+                ErrorCode::BorrowerJurisdictionMismatch
+            );
+        }
+        require!(
+            self.allowed_products & product.bit() != 0,
+            ErrorCode::ProductNotPermitted
+        );
+        require!(
+            interest_rate <= self.max_interest_rate,
+            ErrorCode::UsuryCapExceeded
+        );
+        require!(property_value > 0, ErrorCode::InvalidParameter);
+        let ltv = (loan_amount as u128)
+            .checked_mul(10_000)
+            .ok_or(ErrorCode::Overflow)?
+            / property_value as u128;
+        require!(ltv <= self.max_ltv as u128, ErrorCode::LoanToValueTooHigh);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(jurisdiction: JurisdictionCode)]
+pub struct SetJurisdictionRule<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Passed proposal approving this exact rule
+    #[account(
+        mut,
+        constraint = proposal.status == ProposalStatus::Active @ ErrorCode::InvalidParameter,
+        constraint = proposal.parameter_key == JURISDICTION_RULE_PROPOSAL_KEY @ ErrorCode::InvalidParameter,
+        constraint = Clock::get()?.unix_timestamp >= proposal.execution_time @ ErrorCode::ProposalNotReady,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = JurisdictionRule::LEN,
+        seeds = [b"jurisdiction_rule", jurisdiction.as_ref()],
+        bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetPropertyJurisdiction<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Retagging is refused while a loan holds the property, since its terms were
+    /// checked against the old jurisdiction's rules
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = !property_nft.is_locked @ ErrorCode::NFTAlreadyLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+}
+
+pub fn set_jurisdiction_rule(
+    ctx: Context<SetJurisdictionRule>,
+    jurisdiction: JurisdictionCode,
+    max_interest_rate: u64,
+    max_ltv: u64,
+    disclosures_hash: [u8; 32],
+    allowed_products: u8,
+) -> Result<()> {
+    require!(
+        jurisdiction != JurisdictionCode::default(),
+        ErrorCode::InvalidParameter
+    );
+    require!(max_ltv <= 10_000, ErrorCode::InvalidParameter);
+
+    // Rules change only by executing a passed proposal for the same parameters
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        Clock::get()?.unix_timestamp <= proposal.execution_deadline,
+        ErrorCode::ProposalExpired
+    );
+    require!(proposal.passed()?, ErrorCode::InsufficientVotes);
+    require!(
+        proposal.new_value
+            == JurisdictionRule::proposal_value(
+                &jurisdiction,
+                max_interest_rate,
+                max_ltv,
+                &disclosures_hash,
+                allowed_products,
+            ),
+        ErrorCode::InvalidParameter
+    );
+    proposal.status = ProposalStatus::Executed;
+
+    let jurisdiction_rule = &mut ctx.accounts.jurisdiction_rule;
+    jurisdiction_rule.jurisdiction = jurisdiction;
+    jurisdiction_rule.max_interest_rate = max_interest_rate;
+    jurisdiction_rule.max_ltv = max_ltv;
+    jurisdiction_rule.disclosures_hash = disclosures_hash;
+    jurisdiction_rule.allowed_products = allowed_products;
+    jurisdiction_rule.updated_at = Clock::get()?.unix_timestamp;
+    jurisdiction_rule.bump = ctx.bumps.jurisdiction_rule;
+
+    Ok(())
+}
+
+pub fn set_property_jurisdiction(
+    ctx: Context<SetPropertyJurisdiction>,
+    jurisdiction: JurisdictionCode,
+) -> Result<()> {
+    require!(
+        jurisdiction != JurisdictionCode::default(),
+        ErrorCode::InvalidParameter
+    );
+    ctx.accounts.property_nft.jurisdiction = jurisdiction;
+    Ok(())
+}
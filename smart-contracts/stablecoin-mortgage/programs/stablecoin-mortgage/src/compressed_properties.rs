@@ -0,0 +1,342 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use spl_account_compression::cpi::accounts::{Initialize, Modify};
+use spl_account_compression::program::SplAccountCompression;
+use spl_account_compression::Noop;
+
+use crate::errors::ErrorCode;
+use crate::property_mint::{issue_property_nft, MintPropertyNFT};
+use crate::state::*;
+use crate::title_registry::TitleRegistrar;
+
+/// Config for a concurrent Merkle tree of compressed property records.
+/// The PDA is the tree authority, so only this program can modify leaves.
+///
+/// Compressed records are a registry-only storage mode: they can't be locked,
+/// assessed or lent against. A property has to be decompressed into a full
+/// `PropertyNFT` first, after which the usual assessment, title, property
+/// record and jurisdiction checks apply to it like any other property.
+#[account]
+#[derive(Default)]
+pub struct PropertyTree {
+    /// Merkle tree account
+    pub merkle_tree: Pubkey,
+
+    /// Creator of the tree (e.g. a portfolio manager)
+    pub creator: Pubkey,
+
+    /// Number of properties appended so far; also the next leaf index
+    pub num_properties: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PropertyTree {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // merkle_tree
+                          32 + // creator
+                          8 + // num_properties
+                          1; // bump
+}
+
+/// Property record stored as a Merkle leaf instead of a `PropertyNFT` account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedProperty {
+    /// Owner of the property
+    pub owner: Pubkey,
+
+    /// Leaf index the record was appended at
+    pub nonce: u64,
+
+    /// Declared property value in stablecoin units; not backed by an assessment
+    pub property_value: u64,
+
+    /// Hash of the property address; the full address is in the registration event
+    pub address_hash: [u8; 32],
+
+    /// Jurisdiction the property is located in
+    pub jurisdiction: JurisdictionCode,
+
+    /// Registration date
+    pub registration_date: i64,
+}
+
+impl CompressedProperty {
+    pub fn leaf_hash(&self) -> Result<[u8; 32]> {
+        Ok(keccak::hashv(&[&self.try_to_vec()?]).to_bytes())
+    }
+
+    /// Stable id for the leaf, used by indexers to follow a compressed property
+    pub fn asset_id(&self, merkle_tree: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"compressed_property",
+                merkle_tree.as_ref(),
+                &self.nonce.to_le_bytes(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+#[event]
+pub struct CompressedPropertyUpdated {
+    pub merkle_tree: Pubkey,
+    pub asset_id: Pubkey,
+    pub leaf_index: u32,
+    pub property: CompressedProperty,
+    pub property_address: Option<String>,
+}
+
+#[event]
+pub struct CompressedPropertyDecompressed {
+    pub merkle_tree: Pubkey,
+    pub asset_id: Pubkey,
+    pub leaf_index: u32,
+    pub property_nft: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct CreatePropertyTree<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: zeroed account sized for the tree, initialized by account compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PropertyTree::LEN,
+        seeds = [b"property_tree", merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub property_tree: Account<'info, PropertyTree>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterCompressedProperty<'info> {
+    pub owner: Signer<'info>,
+
+    /// Title registrar co-signing the registration
+    pub registrar: Signer<'info>,
+
+    #[account(
+        seeds = [b"title_registrar", registrar.key().as_ref()],
+        bump = title_registrar.bump,
+        constraint = title_registrar.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        mut,
+        seeds = [b"property_tree", merkle_tree.key().as_ref()],
+        bump = property_tree.bump,
+    )]
+    pub property_tree: Account<'info, PropertyTree>,
+
+    /// CHECK: validated by account compression against `property_tree`
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+}
+
+/// Accounts for replacing a leaf; the Merkle proof is passed as remaining accounts
+#[derive(Accounts)]
+pub struct ModifyCompressedProperty<'info> {
+    #[account(
+        seeds = [b"property_tree", merkle_tree.key().as_ref()],
+        bump = property_tree.bump,
+    )]
+    pub property_tree: Account<'info, PropertyTree>,
+
+    /// CHECK: validated by account compression against `property_tree`
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+}
+
+/// Mints a full property NFT for a compressed record and clears its leaf.
+/// The registrar co-signs the mint exactly as for a fresh registration.
+#[derive(Accounts)]
+pub struct DecompressProperty<'info> {
+    pub property: MintPropertyNFT<'info>,
+
+    pub tree: ModifyCompressedProperty<'info>,
+}
+
+pub fn create_property_tree(
+    ctx: Context<CreatePropertyTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let merkle_tree = ctx.accounts.merkle_tree.key();
+    let seeds = &[
+        b"property_tree".as_ref(),
+        merkle_tree.as_ref(),
+        &[ctx.bumps.property_tree],
+    ];
+    let signer = &[&seeds[..]];
+
+    spl_account_compression::cpi::init_empty_merkle_tree(
+        CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(),
+            Initialize {
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                authority: ctx.accounts.property_tree.to_account_info(),
+                noop: ctx.accounts.log_wrapper.to_account_info(),
+            },
+            signer,
+        ),
+        max_depth,
+        max_buffer_size,
+    )?;
+
+    let property_tree = &mut ctx.accounts.property_tree;
+    property_tree.merkle_tree = merkle_tree;
+    property_tree.creator = ctx.accounts.creator.key();
+    property_tree.num_properties = 0;
+    property_tree.bump = ctx.bumps.property_tree;
+
+    Ok(())
+}
+
+pub fn register_compressed_property(
+    ctx: Context<RegisterCompressedProperty>,
+    property_value: u64,
+    property_address: String,
+    jurisdiction: JurisdictionCode,
+) -> Result<()> {
+    require!(property_value > 0, ErrorCode::InvalidParameter);
+    require!(property_address.len() <= 100, ErrorCode::InvalidParameter);
+
+    let property_tree = &ctx.accounts.property_tree;
+    let property = CompressedProperty {
+        owner: ctx.accounts.owner.key(),
+        nonce: property_tree.num_properties,
+        property_value,
+        address_hash: keccak::hash(property_address.as_bytes()).to_bytes(),
+        jurisdiction,
+        registration_date: Clock::get()?.unix_timestamp,
+    };
+
+    let seeds = &[
+        b"property_tree".as_ref(),
+        property_tree.merkle_tree.as_ref(),
+        &[property_tree.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    spl_account_compression::cpi::append(
+        CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(),
+            Modify {
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                authority: property_tree.to_account_info(),
+                noop: ctx.accounts.log_wrapper.to_account_info(),
+            },
+            signer,
+        ),
+        property.leaf_hash()?,
+    )?;
+
+    let leaf_index = u32::try_from(property.nonce).map_err(|_| ErrorCode::Overflow)?;
+    emit!(CompressedPropertyUpdated {
+        merkle_tree: property_tree.merkle_tree,
+        asset_id: property.asset_id(&property_tree.merkle_tree),
+        leaf_index,
+        property,
+        property_address: Some(property_address),
+    });
+
+    let property_tree = &mut ctx.accounts.property_tree;
+    property_tree.num_properties = property_tree.num_properties
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn decompress_property<'info>(
+    ctx: Context<'_, '_, '_, 'info, DecompressProperty<'info>>,
+    root: [u8; 32],
+    property: CompressedProperty,
+    index: u32,
+    property_address: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    title_evidence_hash: [u8; 32],
+) -> Result<()> {
+    require_keys_eq!(
+        property.owner,
+        ctx.accounts.property.owner.key(),
+        ErrorCode::InvalidNFTOwner
+    );
+    require!(property.nonce == index as u64, ErrorCode::InvalidParameter);
+    require!(
+        keccak::hash(property_address.as_bytes()).to_bytes() == property.address_hash,
+        ErrorCode::InvalidParameter
+    );
+
+    // Clearing the leaf first means the record can't be decompressed twice;
+    // account compression verifies the proof against `root` before writing
+    let tree = &ctx.accounts.tree;
+    let property_tree = &tree.property_tree;
+    let seeds = &[
+        b"property_tree".as_ref(),
+        property_tree.merkle_tree.as_ref(),
+        &[property_tree.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    spl_account_compression::cpi::replace_leaf(
+        CpiContext::new_with_signer(
+            tree.compression_program.to_account_info(),
+            Modify {
+                merkle_tree: tree.merkle_tree.to_account_info(),
+                authority: property_tree.to_account_info(),
+                noop: tree.log_wrapper.to_account_info(),
+            },
+            signer,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        root,
+        property.leaf_hash()?,
+        [0; 32],
+        index,
+    )?;
+
+    emit!(CompressedPropertyDecompressed {
+        merkle_tree: property_tree.merkle_tree,
+        asset_id: property.asset_id(&property_tree.merkle_tree),
+        leaf_index: index,
+        property_nft: ctx.accounts.property.property_nft.key(),
+    });
+
+    // The declared value carries over but, as for any new property, stays
+    // unusable for lending until an assessment confirms it
+    issue_property_nft(
+        &mut ctx.accounts.property,
+        &ctx.bumps.property,
+        property.property_value,
+        property_address,
+        property.jurisdiction,
+        name,
+        symbol,
+        uri,
+        title_evidence_hash,
+    )
+}
@@ -0,0 +1,1010 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_mint::{freeze_property_nft, thaw_property_nft};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+use crate::tranches::apply_tranche_waterfall;
+
+/// Construction inspector registered by the program authority
+#[account]
+#[derive(Default)]
+pub struct Inspector {
+    /// Inspector wallet that signs milestone attestations
+    pub inspector: Pubkey,
+
+    /// Program authority that registered the inspector
+    pub registered_by: Pubkey,
+
+    /// False once the registration has been revoked
+    pub is_active: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Inspector {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // inspector
+                          32 + // registered_by
+                          1 + // is_active
+                          1; // bump
+}
+
+/// Construction loan released in milestone tranches
+#[account]
+#[derive(Default)]
+pub struct ConstructionLoan {
+    /// Borrower wallet
+    pub borrower: Pubkey,
+
+    /// Lending pool providing the loan
+    pub lending_pool: Pubkey,
+
+    /// Property NFT securing the loan
+    pub property_nft: Pubkey,
+
+    /// Total amount committed across all milestones
+    pub committed_amount: u64,
+
+    /// Amount disbursed so far
+    pub disbursed_amount: u64,
+
+    /// Interest accrued on disbursed funds
+    pub accrued_interest: u64,
+
+    /// Interest rate (basis points)
+    pub interest_rate: u64,
+
+    /// Timestamp interest was last accrued to
+    pub last_accrual: i64,
+
+    /// Term of the amortizing mortgage after completion, in seconds
+    pub loan_duration: u64,
+
+    /// Deadline for completion; after it the pool may liquidate the loan
+    pub completion_deadline: i64,
+
+    /// Milestone tranches, disbursed in order
+    pub milestones: Vec<Milestone>,
+
+    /// Index of the next milestone to disburse
+    pub next_milestone: u8,
+
+    /// Mortgage the loan converted into, once complete
+    pub mortgage: Option<Pubkey>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Milestone {
+    /// Amount released when the milestone is drawn
+    pub amount: u64,
+
+    /// Inspector who attested completion of the milestone
+    pub attested_by: Option<Pubkey>,
+
+    /// True once the tranche has been disbursed
+    pub disbursed: bool,
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + // amount
+                          33 + // Option<Pubkey> attested_by
+                          1; // disbursed
+}
+
+impl ConstructionLoan {
+    pub const MAX_MILESTONES: usize = 8;
+
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // borrower
+                          32 + // lending_pool
+                          32 + // property_nft
+                          8 + // committed_amount
+                          8 + // disbursed_amount
+                          8 + // accrued_interest
+                          8 + // interest_rate
+                          8 + // last_accrual
+                          8 + // loan_duration
+                          8 + // completion_deadline
+                          4 + Self::MAX_MILESTONES * Milestone::LEN + // milestones
+                          1 + // next_milestone
+                          33 + // Option<Pubkey> mortgage
+                          1; // bump
+
+    fn accrue(&mut self, now: i64) -> Result<()> {
+        let interest = Mortgage::interest_for(
+            self.disbursed_amount,
+            self.interest_rate,
+            now - self.last_accrual,
+        )?;
+        self.accrued_interest = self.accrued_interest
+            .checked_add(interest)
+            .ok_or(ErrorCode::Overflow)?;
+        self.last_accrual = now;
+        Ok(())
+    }
+
+    /// Books the end of the loan before conversion: disbursed principal is repaid or
+    /// written off, interest paid is income and the shortfall is a loss to the pool
+    fn close_out(
+        &self,
+        lending_pool: &mut LendingPool,
+        borrower_exposure: &mut BorrowerExposure,
+        interest_paid: u64,
+        shortfall: u64,
+        now: i64,
+    ) -> Result<()> {
+        lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(self.disbursed_amount);
+        lending_pool.release_term_liquidity(self.disbursed_amount, self.loan_duration);
+        lending_pool.total_deposited = lending_pool.total_deposited
+            .checked_add(interest_paid)
+            .ok_or(ErrorCode::Overflow)?
+            .saturating_sub(shortfall);
+        lending_pool.last_updated = now;
+        borrower_exposure.sub(self.disbursed_amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RegisterInspector<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: wallet being registered as an inspector
+    pub inspector_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Inspector::LEN,
+        seeds = [b"inspector", inspector_wallet.key().as_ref()],
+        bump,
+    )]
+    pub inspector: Account<'info, Inspector>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeInspector<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"inspector", inspector.inspector.as_ref()],
+        bump = inspector.bump,
+    )]
+    pub inspector: Account<'info, Inspector>,
+}
+
+#[derive(Accounts)]
+pub struct CreateConstructionLoan<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(constraint = lending_pool.is_active @ ErrorCode::PoolInactive)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: borrower KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == borrower.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = !property_nft.is_locked @ ErrorCode::NFTAlreadyLocked,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = ConstructionLoan::LEN,
+        seeds = [b"construction_loan", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub construction_loan: Account<'info, ConstructionLoan>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AttestMilestone<'info> {
+    pub inspector_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"inspector", inspector_wallet.key().as_ref()],
+        bump = inspector.bump,
+        constraint = inspector.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub inspector: Account<'info, Inspector>,
+
+    #[account(
+        mut,
+        seeds = [b"construction_loan", construction_loan.property_nft.as_ref()],
+        bump = construction_loan.bump,
+    )]
+    pub construction_loan: Account<'info, ConstructionLoan>,
+}
+
+#[derive(Accounts)]
+pub struct DrawConstructionMilestone<'info> {
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"construction_loan", construction_loan.property_nft.as_ref()],
+        bump = construction_loan.bump,
+        constraint = construction_loan.borrower == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub construction_loan: Account<'info, ConstructionLoan>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == construction_loan.property_nft @ ErrorCode::InvalidParameter,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == construction_loan.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: borrower KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteConstructionLoan<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"construction_loan", property_nft.key().as_ref()],
+        bump = construction_loan.bump,
+        constraint = construction_loan.borrower == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub construction_loan: Account<'info, ConstructionLoan>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(construction_loan.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == construction_loan.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(init, payer = borrower, space = Mortgage::LEN)]
+    pub mortgage: Account<'info, Mortgage>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConstructionLoan<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"construction_loan", property_nft.key().as_ref()],
+        bump = construction_loan.bump,
+        constraint = construction_loan.borrower == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub construction_loan: Account<'info, ConstructionLoan>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(construction_loan.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == construction_loan.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct LiquidateConstructionLoan<'info> {
+    pub authority: Signer<'info>,
+
+    /// CHECK: borrower receiving the closed loan's rent
+    #[account(mut, address = construction_loan.borrower @ ErrorCode::InvalidParameter)]
+    pub borrower: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"construction_loan", property_nft.key().as_ref()],
+        bump = construction_loan.bump,
+    )]
+    pub construction_loan: Account<'info, ConstructionLoan>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(construction_loan.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == construction_loan.lending_pool @ ErrorCode::InvalidParameter,
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", construction_loan.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    /// Account holding the sale proceeds of the foreclosed property
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Property owner's account receiving the surplus after the loan is repaid
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == property_nft.owner @ ErrorCode::InvalidNFTOwner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn register_inspector(ctx: Context<RegisterInspector>) -> Result<()> {
+    let inspector = &mut ctx.accounts.inspector;
+    inspector.inspector = ctx.accounts.inspector_wallet.key();
+    inspector.registered_by = ctx.accounts.authority.key();
+    inspector.is_active = true;
+    inspector.bump = ctx.bumps.inspector;
+    Ok(())
+}
+
+pub fn revoke_inspector(ctx: Context<RevokeInspector>) -> Result<()> {
+    ctx.accounts.inspector.is_active = false;
+    Ok(())
+}
+
+pub fn create_construction_loan(
+    ctx: Context<CreateConstructionLoan>,
+    milestone_amounts: Vec<u64>,
+    loan_duration: u64,
+    construction_period: i64,
+) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.borrower.key(),
+        PoolAccess::Borrow,
+    )?;
+    let borrower_jurisdiction = enforce_borrower_identity(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &ctx.accounts.borrower.key(),
+    )?;
+
+    let program_state = &ctx.accounts.program_state;
+
+    require!(
+        !milestone_amounts.is_empty()
+            && milestone_amounts.len() <= ConstructionLoan::MAX_MILESTONES,
+        ErrorCode::InvalidMilestones
+    );
+    require!(
+        milestone_amounts.iter().all(|amount| *amount > 0),
+        ErrorCode::InvalidMilestones
+    );
+    let committed_amount = milestone_amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        committed_amount >= program_state.min_loan_amount
+            && committed_amount <= program_state.max_loan_amount,
+        ErrorCode::InvalidLoanAmount
+    );
+    require!(
+        loan_duration >= program_state.min_loan_duration
+            && loan_duration <= program_state.max_loan_duration,
+        ErrorCode::InvalidLoanDuration
+    );
+    require!(construction_period > 0, ErrorCode::InvalidLoanDuration);
+    ctx.accounts.jurisdiction_rule.check_loan(
+        LoanProduct::Construction,
+        ctx.accounts.lending_pool.interest_rate,
+        committed_amount,
+        ctx.accounts.property_nft.property_value,
+        borrower_jurisdiction,
+    )?;
+    // The full commitment must fit the exposure cap; exposure is booked as milestones are drawn
+    enforce_loan_limits(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.pool_limits,
+        &ctx.accounts.borrower_exposure,
+        committed_amount,
+        committed_amount,
+    )?;
+
+    let construction_loan = &mut ctx.accounts.construction_loan;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let clock = Clock::get()?;
+
+    construction_loan.borrower = ctx.accounts.borrower.key();
+    construction_loan.lending_pool = ctx.accounts.lending_pool.key();
+    construction_loan.property_nft = property_nft.key();
+    construction_loan.committed_amount = committed_amount;
+    construction_loan.disbursed_amount = 0;
+    construction_loan.accrued_interest = 0;
+    construction_loan.interest_rate = ctx.accounts.lending_pool.interest_rate;
+    construction_loan.last_accrual = clock.unix_timestamp;
+    construction_loan.loan_duration = loan_duration;
+    construction_loan.completion_deadline = clock.unix_timestamp
+        .checked_add(construction_period)
+        .ok_or(ErrorCode::Overflow)?;
+    construction_loan.milestones = milestone_amounts
+        .into_iter()
+        .map(|amount| Milestone {
+            amount,
+            attested_by: None,
+            disbursed: false,
+        })
+        .collect();
+    construction_loan.next_milestone = 0;
+    construction_loan.mortgage = None;
+    construction_loan.bump = ctx.bumps.construction_loan;
+
+    property_nft.is_locked = true;
+    property_nft.locked_by = Some(construction_loan.key());
+
+    freeze_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &ctx.accounts.borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    Ok(())
+}
+
+pub fn attest_milestone(ctx: Context<AttestMilestone>, milestone_index: u8) -> Result<()> {
+    let construction_loan = &mut ctx.accounts.construction_loan;
+
+    // Milestones are attested and drawn strictly in order
+    require!(
+        milestone_index == construction_loan.next_milestone,
+        ErrorCode::InvalidMilestones
+    );
+    let milestone = construction_loan
+        .milestones
+        .get_mut(milestone_index as usize)
+        .ok_or(ErrorCode::InvalidMilestones)?;
+    require!(milestone.attested_by.is_none(), ErrorCode::MilestoneAlreadyAttested);
+
+    milestone.attested_by = Some(ctx.accounts.inspector_wallet.key());
+
+    Ok(())
+}
+
+pub fn draw_construction_milestone(ctx: Context<DrawConstructionMilestone>) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.borrower.key(),
+        PoolAccess::Borrow,
+    )?;
+    enforce_borrower_identity(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &ctx.accounts.borrower.key(),
+    )?;
+
+    let construction_loan = &mut ctx.accounts.construction_loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let clock = Clock::get()?;
+
+    let index = construction_loan.next_milestone as usize;
+    let milestone = construction_loan
+        .milestones
+        .get(index)
+        .ok_or(ErrorCode::InvalidMilestones)?;
+    require!(milestone.attested_by.is_some(), ErrorCode::MilestoneNotAttested);
+    let amount = milestone.amount;
+
+    require!(
+        lending_pool.available_liquidity() >= amount,
+        ErrorCode::InsufficientLiquidity
+    );
+    enforce_loan_limits(
+        lending_pool,
+        &ctx.accounts.pool_limits,
+        &ctx.accounts.borrower_exposure,
+        construction_loan.committed_amount,
+        amount,
+    )?;
+    lending_pool.match_term_liquidity(amount, construction_loan.loan_duration)?;
+
+    construction_loan.accrue(clock.unix_timestamp)?;
+
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        lending_pool.authority.as_ref(),
+        &[lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stablecoin_vault.to_account_info(),
+                to: ctx.accounts.borrower_token_account.to_account_info(),
+                authority: lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    construction_loan.milestones[index].disbursed = true;
+    construction_loan.next_milestone += 1;
+    construction_loan.disbursed_amount = construction_loan.disbursed_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    ctx.accounts.borrower_exposure.add(amount)?;
+
+    lending_pool.total_borrowed = lending_pool.total_borrowed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn complete_construction_loan(ctx: Context<CompleteConstructionLoan>) -> Result<()> {
+    let construction_loan = &mut ctx.accounts.construction_loan;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let mortgage = &mut ctx.accounts.mortgage;
+    let clock = Clock::get()?;
+
+    require!(
+        construction_loan.milestones.iter().all(|milestone| milestone.disbursed),
+        ErrorCode::MilestonesOutstanding
+    );
+
+    construction_loan.accrue(clock.unix_timestamp)?;
+
+    // Interest accrued during construction is capitalized into the mortgage
+    let loan_amount = construction_loan.disbursed_amount
+        .checked_add(construction_loan.accrued_interest)
+        .ok_or(ErrorCode::Overflow)?;
+    let months = construction_loan.loan_duration / Mortgage::PAYMENT_INTERVAL as u64;
+
+    // The pool books the capitalized interest as accrued income, leaving liquidity unchanged
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.total_borrowed = lending_pool.total_borrowed
+        .checked_add(construction_loan.accrued_interest)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(construction_loan.accrued_interest)
+        .ok_or(ErrorCode::Overflow)?;
+    // Disbursed principal was matched draw by draw; only the capitalized interest is new
+    lending_pool.match_term_liquidity(
+        construction_loan.accrued_interest,
+        construction_loan.loan_duration,
+    )?;
+    lending_pool.last_updated = clock.unix_timestamp;
+    apply_tranche_waterfall(
+        &lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        construction_loan.accrued_interest,
+        0,
+    )?;
+    // Exposure tracks the mortgage principal, which now includes the capitalized interest
+    ctx.accounts.borrower_exposure.add(construction_loan.accrued_interest)?;
+
+    mortgage.borrower = construction_loan.borrower;
+    mortgage.lending_pool = construction_loan.lending_pool;
+    mortgage.property_nft = property_nft.key();
+    mortgage.property_nft_mint = property_nft.mint;
+    mortgage.loan_amount = loan_amount;
+    mortgage.property_value = property_nft.property_value;
+    mortgage.loan_duration = construction_loan.loan_duration;
+    mortgage.interest_rate = construction_loan.interest_rate;
+    mortgage.monthly_payment =
+        Mortgage::amortized_payment(loan_amount, construction_loan.interest_rate, months)?;
+    mortgage.remaining_balance = loan_amount;
+    mortgage.next_payment_due = clock.unix_timestamp + Mortgage::PAYMENT_INTERVAL;
+    mortgage.payments_made = 0;
+    mortgage.is_active = true;
+    mortgage.is_default = false;
+    mortgage.funding_date = clock.unix_timestamp;
+    mortgage.close_date = None;
+
+    construction_loan.mortgage = Some(mortgage.key());
+    property_nft.locked_by = Some(mortgage.key());
+
+    Ok(())
+}
+
+/// Repays everything drawn plus accrued interest and releases the property
+/// before the loan converts into a mortgage
+pub fn cancel_construction_loan(ctx: Context<CancelConstructionLoan>) -> Result<()> {
+    let construction_loan = &mut ctx.accounts.construction_loan;
+    let clock = Clock::get()?;
+
+    require!(construction_loan.mortgage.is_none(), ErrorCode::InvalidParameter);
+
+    construction_loan.accrue(clock.unix_timestamp)?;
+    let interest = construction_loan.accrued_interest;
+    let payoff = construction_loan.disbursed_amount
+        .checked_add(interest)
+        .ok_or(ErrorCode::Overflow)?;
+
+    if payoff > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.borrower_token_account.to_account_info(),
+                    to: ctx.accounts.stablecoin_vault.to_account_info(),
+                    authority: ctx.accounts.borrower.to_account_info(),
+                },
+            ),
+            payoff,
+        )?;
+    }
+
+    construction_loan.close_out(
+        &mut ctx.accounts.lending_pool,
+        &mut ctx.accounts.borrower_exposure,
+        interest,
+        0,
+        clock.unix_timestamp,
+    )?;
+    apply_tranche_waterfall(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        interest,
+        0,
+    )?;
+
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &ctx.accounts.borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )
+}
+
+/// Settles a loan that missed its completion deadline out of the foreclosure sale
+/// proceeds: interest first, then principal, with any shortfall written off and
+/// the surplus paid to the owner
+pub fn liquidate_construction_loan(
+    ctx: Context<LiquidateConstructionLoan>,
+    proceeds: u64,
+) -> Result<()> {
+    let construction_loan = &mut ctx.accounts.construction_loan;
+    let clock = Clock::get()?;
+
+    require!(proceeds > 0, ErrorCode::InvalidParameter);
+    require!(construction_loan.mortgage.is_none(), ErrorCode::InvalidParameter);
+    require!(
+        clock.unix_timestamp > construction_loan.completion_deadline,
+        ErrorCode::ConstructionPeriodActive
+    );
+
+    construction_loan.accrue(clock.unix_timestamp)?;
+    let interest_paid = proceeds.min(construction_loan.accrued_interest);
+    let principal_paid = (proceeds - interest_paid).min(construction_loan.disbursed_amount);
+    let total_paid = interest_paid + principal_paid;
+    let shortfall = construction_loan.disbursed_amount - principal_paid;
+
+    if total_paid > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.stablecoin_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            total_paid,
+        )?;
+    }
+    let surplus = proceeds - total_paid;
+    if surplus > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            surplus,
+        )?;
+    }
+
+    construction_loan.close_out(
+        &mut ctx.accounts.lending_pool,
+        &mut ctx.accounts.borrower_exposure,
+        interest_paid,
+        shortfall,
+        clock.unix_timestamp,
+    )?;
+    apply_tranche_waterfall(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        interest_paid,
+        shortfall,
+    )?;
+
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &property_nft.owner,
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )
+}
@@ -41,6 +41,8 @@ pub enum ErrorCode {
     NFTNotLocked,
     #[msg("Invalid NFT owner")]
     InvalidNFTOwner,
+    #[msg("Property NFT mint is not frozen by the program")]
+    PropertyNotFreezable,
     
     // Rewards errors
     #[msg("No rewards available to claim")]
@@ -75,4 +77,164 @@ pub enum ErrorCode {
     ProposalNotReady,
     #[msg("Proposal already executed")]
     ProposalAlreadyExecuted,
+    #[msg("No governance tokens escrowed")]
+    NoVotingPower,
+    #[msg("Governance tokens are locked by an active vote")]
+    GovernanceTokensLocked,
+    #[msg("Lock end must extend the current lock within the maximum duration")]
+    InvalidLockEnd,
+    #[msg("Proposal is not open for voting")]
+    VotingClosed,
+    #[msg("Proposal execution window has passed")]
+    ProposalExpired,
+    #[msg("Voting power below proposer threshold")]
+    BelowProposerThreshold,
+    #[msg("Aggregate voting power must be checkpointed to the current time")]
+    GovernanceCheckpointStale,
+    
+    // Blanket mortgage errors
+    #[msg("Too many properties in collateral set")]
+    TooManyProperties,
+    #[msg("Property is not part of this collateral set")]
+    PropertyNotInCollateral,
+    #[msg("Property already released")]
+    PropertyAlreadyReleased,
+    #[msg("Release would exceed allowed loan-to-value")]
+    ReleaseExceedsLtv,
+    #[msg("Liquidation proceeds already settled")]
+    ProceedsAlreadySettled,
+    
+    // Lien errors
+    #[msg("Too many liens on property")]
+    TooManyLiens,
+    #[msg("Combined loan-to-value limit exceeded")]
+    CombinedLtvExceeded,
+    #[msg("Lien not found in registry")]
+    LienNotFound,
+    #[msg("Lien still has an outstanding balance")]
+    LienOutstanding,
+    #[msg("Lien accounts do not match the registry")]
+    InvalidLienAccounts,
+    
+    // HELOC errors
+    #[msg("Credit limit exceeded")]
+    CreditLimitExceeded,
+    #[msg("Draw period has ended")]
+    DrawPeriodEnded,
+    #[msg("Draw period is still active")]
+    DrawPeriodActive,
+    #[msg("No payment is overdue")]
+    PaymentNotOverdue,
+    
+    // Construction loan errors
+    #[msg("Invalid milestone schedule")]
+    InvalidMilestones,
+    #[msg("Milestone already attested")]
+    MilestoneAlreadyAttested,
+    #[msg("Milestone has not been attested by an inspector")]
+    MilestoneNotAttested,
+    #[msg("Not all milestones have been disbursed")]
+    MilestonesOutstanding,
+    #[msg("Construction period has not ended")]
+    ConstructionPeriodActive,
+    
+    // Closing escrow errors
+    #[msg("Escrow already funded")]
+    EscrowAlreadyFunded,
+    #[msg("Escrow not funded")]
+    EscrowNotFunded,
+    #[msg("Purchase price does not cover the mortgage payoff")]
+    PurchasePriceBelowPayoff,
+    #[msg("Buyer financing has not been approved by the lending pool")]
+    FinancingNotApproved,
+    
+    // Assumption errors
+    #[msg("Assumption has not been approved")]
+    AssumptionNotApproved,
+    
+    // Refinance errors
+    #[msg("Loan-to-value ratio too high")]
+    LoanToValueTooHigh,
+    
+    // Syndication errors
+    #[msg("Invalid syndicate participations")]
+    InvalidParticipations,
+    #[msg("Participation already funded")]
+    ParticipationAlreadyFunded,
+    #[msg("Syndicate is not fully funded")]
+    SyndicateNotFunded,
+    #[msg("Participation already written off")]
+    ParticipationWrittenOff,
+    
+    // Tranche errors
+    #[msg("Tranche has been fully written down")]
+    TrancheWrittenDown,
+    
+    // Term deposit errors
+    #[msg("Unsupported deposit term")]
+    InvalidDepositTerm,
+    #[msg("Deposit has not reached maturity")]
+    DepositNotMatured,
+    #[msg("Not enough committed term liquidity for a long-dated loan")]
+    InsufficientTermLiquidity,
+    #[msg("Term boost reserve cannot cover the boost")]
+    InsufficientBoostReserve,
+    
+    // Withdrawal queue errors
+    #[msg("Withdrawal can be served immediately")]
+    WithdrawalNotQueueable,
+    #[msg("Withdrawal request already closed")]
+    WithdrawalRequestClosed,
+    #[msg("Queued withdrawals must be processed first")]
+    WithdrawalQueueNotEmpty,
+    
+    // Pool limit errors
+    #[msg("Amount exceeds per-lender deposit limit")]
+    ExceedsLenderLimit,
+    #[msg("Loan exceeds pool concentration limit")]
+    ExceedsLoanConcentration,
+    #[msg("Loan exceeds borrower exposure limit")]
+    ExceedsBorrowerExposure,
+    
+    // Allowlist errors
+    #[msg("Address is not allowlisted for this pool")]
+    NotAllowlisted,
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+    
+    // Identity errors
+    #[msg("Borrower identity attestation missing")]
+    IdentityMissing,
+    #[msg("Borrower identity attestation revoked")]
+    IdentityRevoked,
+    #[msg("Borrower verification level too low")]
+    InsufficientVerificationLevel,
+    #[msg("Identity provider is not registered or has been revoked")]
+    IdentityProviderInactive,
+    
+    // Compliance errors
+    #[msg("Loan product not permitted in this jurisdiction")]
+    ProductNotPermitted,
+    #[msg("Interest rate exceeds jurisdiction usury cap")]
+    UsuryCapExceeded,
+    #[msg("Borrower is not verified in the property's jurisdiction")]
+    BorrowerJurisdictionMismatch,
+    
+    // Property record errors
+    #[msg("Invalid parcel identifier")]
+    InvalidParcelId,
+    #[msg("Property already has a record")]
+    PropertyAlreadyRecorded,
+    #[msg("Too many documents attached to property record")]
+    TooManyDocuments,
+    #[msg("Property has no linked property record")]
+    PropertyRecordMissing,
+    
+    // Title errors
+    #[msg("Property has no current title attestation")]
+    TitleNotCurrent,
+    
+    // Fractionalization errors
+    #[msg("Not all fractions are held by the caller")]
+    FractionsOutstanding,
 }
@@ -0,0 +1,823 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_records::PropertyRecord;
+use crate::risk_assessment::RiskAssessment;
+use crate::state::*;
+use crate::tranches::apply_tranche_waterfall;
+
+/// Vault holding a property NFT that has been split into fraction tokens
+#[account]
+#[derive(Default)]
+pub struct FractionVault {
+    /// Fractionalized property NFT
+    pub property_nft: Pubkey,
+
+    /// Owner who fractionalized the property
+    pub originator: Pubkey,
+
+    /// SPL mint of the fraction tokens
+    pub fraction_mint: Pubkey,
+
+    /// Token account holding the property NFT
+    pub nft_vault: Pubkey,
+
+    /// Fixed number of fractions
+    pub total_supply: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FractionVault {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // property_nft
+                          32 + // originator
+                          32 + // fraction_mint
+                          32 + // nft_vault
+                          8 + // total_supply
+                          1; // bump
+
+    /// Value of `fractions` as a pro rata share of `property_value`
+    pub fn fraction_value(&self, fractions: u64, property_value: u64) -> Result<u64> {
+        let value = (property_value as u128)
+            .checked_mul(fractions as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / self.total_supply as u128;
+        u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+    }
+}
+
+/// Loan secured by pledged property fractions
+#[account]
+#[derive(Default)]
+pub struct FractionLoan {
+    /// Borrower who pledged the fractions
+    pub borrower: Pubkey,
+
+    /// Lending pool that funded the loan
+    pub lending_pool: Pubkey,
+
+    /// Fraction vault of the pledged property
+    pub fraction_vault: Pubkey,
+
+    /// Borrower-chosen id distinguishing loans against the same vault
+    pub loan_id: u64,
+
+    /// Token account holding the pledged fractions
+    pub pledge_vault: Pubkey,
+
+    /// Number of fractions pledged
+    pub fractions_pledged: u64,
+
+    /// Outstanding principal
+    pub principal: u64,
+
+    /// Interest accrued and not yet paid
+    pub accrued_interest: u64,
+
+    /// Annual interest rate (basis points)
+    pub interest_rate: u64,
+
+    /// Timestamp interest was last accrued to
+    pub last_accrual: i64,
+
+    /// Date the loan must be repaid by
+    pub maturity_date: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FractionLoan {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // borrower
+                          32 + // lending_pool
+                          32 + // fraction_vault
+                          8 + // loan_id
+                          32 + // pledge_vault
+                          8 + // fractions_pledged
+                          8 + // principal
+                          8 + // accrued_interest
+                          8 + // interest_rate
+                          8 + // last_accrual
+                          8 + // maturity_date
+                          1; // bump
+
+    /// Maximum loan as a share of the pledged fractions' value (basis points).
+    /// Lower than whole-property loans since fractions are harder to sell.
+    pub const MAX_LTV: u64 = 6_000;
+
+    fn accrue(&mut self, now: i64) -> Result<()> {
+        let interest = Mortgage::interest_for(
+            self.principal,
+            self.interest_rate,
+            now - self.last_accrual,
+        )?;
+        self.accrued_interest = self.accrued_interest
+            .checked_add(interest)
+            .ok_or(ErrorCode::Overflow)?;
+        self.last_accrual = now;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct FractionalizeProperty<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == owner.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = !property_nft.is_locked @ ErrorCode::NFTAlreadyLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_nft_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub owner_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = FractionVault::LEN,
+        seeds = [b"fraction_vault", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = property_mint,
+        token::authority = fraction_vault,
+    )]
+    pub nft_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = fraction_vault,
+    )]
+    pub fraction_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = fraction_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_fraction_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ReassembleProperty<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(fraction_vault.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"fraction_vault", property_nft.key().as_ref()],
+        bump = fraction_vault.bump,
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        mut,
+        constraint = nft_vault.key() == fraction_vault.nft_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub nft_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fraction_mint.key() == fraction_vault.fraction_mint @ ErrorCode::InvalidParameter,
+    )]
+    pub fraction_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = holder_fraction_account.owner == holder.key() @ ErrorCode::Unauthorized,
+        constraint = holder_fraction_account.mint == fraction_mint.key() @ ErrorCode::InvalidParameter,
+        constraint = holder_fraction_account.amount == fraction_vault.total_supply @ ErrorCode::FractionsOutstanding,
+    )]
+    pub holder_fraction_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = holder_nft_account.owner == holder.key() @ ErrorCode::Unauthorized,
+        constraint = holder_nft_account.mint == property_nft.mint @ ErrorCode::InvalidParameter,
+    )]
+    pub holder_nft_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(loan_id: u64)]
+pub struct PledgeFractions<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(mut, constraint = lending_pool.is_active @ ErrorCode::PoolInactive)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: borrower KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        seeds = [b"fraction_vault", fraction_vault.property_nft.as_ref()],
+        bump = fraction_vault.bump,
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == fraction_vault.property_nft @ ErrorCode::InvalidParameter,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    #[account(
+        seeds = [b"risk_assessment", property_nft.key().as_ref()],
+        bump = risk_assessment.bump,
+        constraint = risk_assessment.is_valid @ ErrorCode::InvalidAssessment,
+    )]
+    pub risk_assessment: Account<'info, RiskAssessment>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = FractionLoan::LEN,
+        seeds = [
+            b"fraction_loan",
+            fraction_vault.key().as_ref(),
+            borrower.key().as_ref(),
+            &loan_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub fraction_loan: Account<'info, FractionLoan>,
+
+    #[account(constraint = fraction_mint.key() == fraction_vault.fraction_mint @ ErrorCode::InvalidParameter)]
+    pub fraction_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = borrower,
+        token::mint = fraction_mint,
+        token::authority = fraction_loan,
+    )]
+    pub pledge_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = borrower_fraction_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_fraction_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RepayFractionLoan<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub fraction_loan: Account<'info, FractionLoan>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == fraction_loan.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", fraction_loan.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleasePledgedFractions<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        close = borrower,
+        constraint = fraction_loan.borrower == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub fraction_loan: Account<'info, FractionLoan>,
+
+    #[account(
+        mut,
+        constraint = pledge_vault.key() == fraction_loan.pledge_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub pledge_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = borrower_fraction_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_fraction_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct LiquidateFractionLoan<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == fraction_loan.lending_pool @ ErrorCode::InvalidParameter,
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    /// CHECK: borrower receiving the loan account's rent
+    #[account(mut, constraint = borrower.key() == fraction_loan.borrower @ ErrorCode::InvalidParameter)]
+    pub borrower: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(mut, close = borrower)]
+    pub fraction_loan: Account<'info, FractionLoan>,
+
+    #[account(
+        mut,
+        constraint = pledge_vault.key() == fraction_loan.pledge_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub pledge_vault: Account<'info, TokenAccount>,
+
+    /// Pool authority's account receiving the seized fractions
+    #[account(
+        mut,
+        constraint = recovery_fraction_account.owner == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub recovery_fraction_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn fractionalize_property(ctx: Context<FractionalizeProperty>, total_supply: u64) -> Result<()> {
+    require!(total_supply > 1, ErrorCode::InvalidParameter);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.owner_nft_account.to_account_info(),
+                to: ctx.accounts.nft_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    let property_nft_key = ctx.accounts.property_nft.key();
+    let seeds = &[
+        b"fraction_vault".as_ref(),
+        property_nft_key.as_ref(),
+        &[ctx.bumps.fraction_vault],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.fraction_mint.to_account_info(),
+                to: ctx.accounts.owner_fraction_account.to_account_info(),
+                authority: ctx.accounts.fraction_vault.to_account_info(),
+            },
+            signer,
+        ),
+        total_supply,
+    )?;
+
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.property_nft = property_nft_key;
+    fraction_vault.originator = ctx.accounts.owner.key();
+    fraction_vault.fraction_mint = ctx.accounts.fraction_mint.key();
+    fraction_vault.nft_vault = ctx.accounts.nft_vault.key();
+    fraction_vault.total_supply = total_supply;
+    fraction_vault.bump = ctx.bumps.fraction_vault;
+
+    // The whole NFT can't be pledged while its fractions are outstanding
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.token_account = ctx.accounts.nft_vault.key();
+    property_nft.is_locked = true;
+    property_nft.locked_by = Some(fraction_vault.key());
+
+    Ok(())
+}
+
+pub fn reassemble_property(ctx: Context<ReassembleProperty>) -> Result<()> {
+    let fraction_vault = &ctx.accounts.fraction_vault;
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.fraction_mint.to_account_info(),
+                from: ctx.accounts.holder_fraction_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        fraction_vault.total_supply,
+    )?;
+
+    let seeds = &[
+        b"fraction_vault".as_ref(),
+        fraction_vault.property_nft.as_ref(),
+        &[fraction_vault.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.nft_vault.to_account_info(),
+                to: ctx.accounts.holder_nft_account.to_account_info(),
+                authority: fraction_vault.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::CloseAccount {
+            account: ctx.accounts.nft_vault.to_account_info(),
+            destination: ctx.accounts.holder.to_account_info(),
+            authority: fraction_vault.to_account_info(),
+        },
+        signer,
+    ))?;
+
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.owner = ctx.accounts.holder.key();
+    property_nft.token_account = ctx.accounts.holder_nft_account.key();
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    Ok(())
+}
+
+pub fn pledge_fractions(
+    ctx: Context<PledgeFractions>,
+    loan_id: u64,
+    fractions: u64,
+    loan_amount: u64,
+) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.borrower.key(),
+        PoolAccess::Borrow,
+    )?;
+    let borrower_jurisdiction = enforce_borrower_identity(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &ctx.accounts.borrower.key(),
+    )?;
+
+    require!(fractions > 0, ErrorCode::InvalidParameter);
+    require!(loan_amount > 0, ErrorCode::InvalidLoanAmount);
+
+    let collateral_value = ctx.accounts.fraction_vault
+        .fraction_value(fractions, ctx.accounts.risk_assessment.appraised_value)?;
+    require!(collateral_value > 0, ErrorCode::InvalidParameter);
+    let ltv = (loan_amount as u128)
+        .checked_mul(10_000)
+        .ok_or(ErrorCode::Overflow)?
+        / collateral_value as u128;
+    require!(ltv <= FractionLoan::MAX_LTV as u128, ErrorCode::LoanToValueTooHigh);
+    ctx.accounts.jurisdiction_rule.check_loan(
+        LoanProduct::Fractional,
+        ctx.accounts.lending_pool.interest_rate,
+        loan_amount,
+        collateral_value,
+        borrower_jurisdiction,
+    )?;
+    require!(
+        ctx.accounts.lending_pool.available_liquidity() >= loan_amount,
+        ErrorCode::InsufficientLiquidity
+    );
+    enforce_loan_limits(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.pool_limits,
+        &ctx.accounts.borrower_exposure,
+        loan_amount,
+        loan_amount,
+    )?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.borrower_fraction_account.to_account_info(),
+                to: ctx.accounts.pledge_vault.to_account_info(),
+                authority: ctx.accounts.borrower.to_account_info(),
+            },
+        ),
+        fractions,
+    )?;
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        lending_pool.authority.as_ref(),
+        &[lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stablecoin_vault.to_account_info(),
+                to: ctx.accounts.borrower_token_account.to_account_info(),
+                authority: lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        loan_amount,
+    )?;
+
+    let clock = Clock::get()?;
+    lending_pool.total_borrowed = lending_pool.total_borrowed
+        .checked_add(loan_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+    ctx.accounts.borrower_exposure.add(loan_amount)?;
+
+    let fraction_loan = &mut ctx.accounts.fraction_loan;
+    fraction_loan.borrower = ctx.accounts.borrower.key();
+    fraction_loan.lending_pool = lending_pool.key();
+    fraction_loan.fraction_vault = ctx.accounts.fraction_vault.key();
+    fraction_loan.loan_id = loan_id;
+    fraction_loan.pledge_vault = ctx.accounts.pledge_vault.key();
+    fraction_loan.fractions_pledged = fractions;
+    fraction_loan.principal = loan_amount;
+    fraction_loan.accrued_interest = 0;
+    fraction_loan.interest_rate = lending_pool.interest_rate;
+    fraction_loan.last_accrual = clock.unix_timestamp;
+    fraction_loan.maturity_date = clock.unix_timestamp
+        .checked_add(lending_pool.loan_duration as i64)
+        .ok_or(ErrorCode::Overflow)?;
+    fraction_loan.bump = ctx.bumps.fraction_loan;
+
+    Ok(())
+}
+
+pub fn repay_fraction_loan(ctx: Context<RepayFractionLoan>, amount: u64) -> Result<()> {
+    let fraction_loan = &mut ctx.accounts.fraction_loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, ErrorCode::InsufficientPayment);
+
+    fraction_loan.accrue(clock.unix_timestamp)?;
+
+    // Interest is settled before principal; overpayment is capped at what is owed
+    let interest_paid = amount.min(fraction_loan.accrued_interest);
+    let principal_paid = (amount - interest_paid).min(fraction_loan.principal);
+    let total_paid = interest_paid + principal_paid;
+    require!(total_paid > 0, ErrorCode::InsufficientPayment);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                to: ctx.accounts.stablecoin_vault.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        total_paid,
+    )?;
+
+    fraction_loan.accrued_interest -= interest_paid;
+    fraction_loan.principal -= principal_paid;
+    ctx.accounts.borrower_exposure.sub(principal_paid);
+
+    lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(principal_paid);
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(interest_paid)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    apply_tranche_waterfall(
+        &lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        interest_paid,
+        0,
+    )?;
+
+    Ok(())
+}
+
+pub fn release_pledged_fractions(ctx: Context<ReleasePledgedFractions>) -> Result<()> {
+    let fraction_loan = &mut ctx.accounts.fraction_loan;
+
+    fraction_loan.accrue(Clock::get()?.unix_timestamp)?;
+    require!(
+        fraction_loan.principal == 0 && fraction_loan.accrued_interest == 0,
+        ErrorCode::LienOutstanding
+    );
+
+    transfer_pledge(
+        fraction_loan,
+        &ctx.accounts.pledge_vault,
+        &ctx.accounts.borrower_fraction_account,
+        &ctx.accounts.borrower.to_account_info(),
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn liquidate_fraction_loan(ctx: Context<LiquidateFractionLoan>) -> Result<()> {
+    let fraction_loan = &mut ctx.accounts.fraction_loan;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= fraction_loan.maturity_date && fraction_loan.principal > 0,
+        ErrorCode::NoPaymentDue
+    );
+
+    transfer_pledge(
+        fraction_loan,
+        &ctx.accounts.pledge_vault,
+        &ctx.accounts.recovery_fraction_account,
+        &ctx.accounts.borrower.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // The unpaid principal is written off; the pool recovers it by selling the
+    // seized fractions and depositing the proceeds
+    let written_off = fraction_loan.principal;
+    lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(written_off);
+    lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(written_off);
+    lending_pool.last_updated = clock.unix_timestamp;
+    ctx.accounts.borrower_exposure.sub(written_off);
+
+    apply_tranche_waterfall(&lending_pool.key(), &ctx.accounts.pool_tranches, 0, written_off)?;
+
+    Ok(())
+}
+
+/// Moves the pledged fractions out of escrow and closes the pledge vault
+fn transfer_pledge<'info>(
+    fraction_loan: &Account<'info, FractionLoan>,
+    pledge_vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    rent_receiver: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let loan_id = fraction_loan.loan_id.to_le_bytes();
+    let seeds = &[
+        b"fraction_loan".as_ref(),
+        fraction_loan.fraction_vault.as_ref(),
+        fraction_loan.borrower.as_ref(),
+        loan_id.as_ref(),
+        &[fraction_loan.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: pledge_vault.to_account_info(),
+                to: destination.to_account_info(),
+                authority: fraction_loan.to_account_info(),
+            },
+            signer,
+        ),
+        fraction_loan.fractions_pledged,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::CloseAccount {
+            account: pledge_vault.to_account_info(),
+            destination: rent_receiver.clone(),
+            authority: fraction_loan.to_account_info(),
+        },
+        signer,
+    ))
+}
@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
 
 #[account]
 #[derive(Default)]
@@ -12,6 +16,10 @@ pub struct Proposal {
     pub status: ProposalStatus,
     pub parameter_key: String,
     pub new_value: u64,
+    pub voting_start: i64,
+    pub voting_end: i64,
+    pub execution_deadline: i64,
+    pub quorum_votes: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default)]
@@ -33,11 +41,26 @@ impl Proposal {
                                8 + // no_votes
                                1 + // status
                                4 + // parameter_key string prefix
-                               8; // new_value
+                               8 + // new_value
+                               8 + // voting_start
+                               8 + // voting_end
+                               8 + // execution_deadline
+                               8; // quorum_votes
     
     pub const MAX_TITLE_LEN: usize = 100;
     pub const MAX_DESCRIPTION_LEN: usize = 500;
     pub const MAX_PARAMETER_KEY_LEN: usize = 50;
+    
+    /// Longest timelock a proposer may set between voting end and execution (30 days)
+    pub const MAX_EXECUTION_DELAY: i64 = 30 * 24 * 60 * 60;
+    
+    /// True if enough votes were cast and a majority supported the proposal
+    pub fn passed(&self) -> Result<bool> {
+        let total_votes = self.yes_votes
+            .checked_add(self.no_votes)
+            .ok_or(ErrorCode::VoteOverflow)?;
+        Ok(total_votes >= self.quorum_votes && self.yes_votes > self.no_votes)
+    }
 }
 
 #[account]
@@ -57,11 +80,249 @@ impl Vote {
                           1; // support
 }
 
+/// Governance token settings, managed by the program authority
+#[account]
+#[derive(Default)]
+pub struct GovernanceConfig {
+    /// Program authority that configured governance
+    pub authority: Pubkey,
+
+    /// SPL mint of the governance token
+    pub governance_mint: Pubkey,
+
+    /// Delay between proposal creation and the start of voting
+    pub voting_delay: i64,
+
+    /// Length of the voting window
+    pub voting_period: i64,
+
+    /// Votes needed for a valid outcome, as a share of aggregate voting power (basis points)
+    pub quorum: u64,
+
+    /// Voting power needed to create a proposal
+    pub proposer_threshold: u64,
+
+    /// Time after `execution_time` before a passed proposal expires
+    pub execution_grace_period: i64,
+
+    /// Aggregate voting power at `ve_checkpoint`, scaled by `VoterEscrow::MAX_LOCK_DURATION`
+    pub ve_bias: u128,
+
+    /// Rate at which `ve_bias` decays per second: the escrowed amount under unexpired locks
+    pub ve_slope: u128,
+
+    /// Time the aggregate voting power was last brought forward
+    pub ve_checkpoint: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl GovernanceConfig {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // authority
+                          32 + // governance_mint
+                          8 + // voting_delay
+                          8 + // voting_period
+                          8 + // quorum
+                          8 + // proposer_threshold
+                          8 + // execution_grace_period
+                          16 + // ve_bias
+                          16 + // ve_slope
+                          8 + // ve_checkpoint
+                          1; // bump
+
+    /// Lock ends are week-aligned so slope changes can be bucketed per week
+    pub const WEEK: i64 = 7 * 24 * 60 * 60;
+
+    /// Lowest quorum governance may be configured with (1%, basis points)
+    pub const MIN_QUORUM: u64 = 100;
+
+    pub fn set_params(
+        &mut self,
+        voting_delay: i64,
+        voting_period: i64,
+        quorum: u64,
+        proposer_threshold: u64,
+        execution_grace_period: i64,
+    ) -> Result<()> {
+        require!(
+            voting_delay >= 0 && voting_period > 0 && execution_grace_period > 0,
+            ErrorCode::InvalidParameter
+        );
+        require!(
+            (Self::MIN_QUORUM..=10_000).contains(&quorum),
+            ErrorCode::InvalidParameter
+        );
+        self.voting_delay = voting_delay;
+        self.voting_period = voting_period;
+        self.quorum = quorum;
+        self.proposer_threshold = proposer_threshold;
+        self.execution_grace_period = execution_grace_period;
+        Ok(())
+    }
+
+    /// Aggregate voting power of all escrows as of `ve_checkpoint`
+    pub fn total_voting_power(&self) -> Result<u64> {
+        let power = self.ve_bias / VoterEscrow::MAX_LOCK_DURATION as u128;
+        u64::try_from(power).map_err(|_| ErrorCode::Overflow.into())
+    }
+
+    /// Brings the aggregate voting power forward to `now`, removing the slope of
+    /// locks that ended at each week boundary crossed. `slope_changes` holds the
+    /// `["ve_slope_change", week]` PDAs for those boundaries in order; an
+    /// uninitialized PDA means no lock ended that week. Stops at the last
+    /// boundary covered if the list runs out, so a long gap can be caught up
+    /// over several calls.
+    pub fn checkpoint(&mut self, now: i64, slope_changes: &[AccountInfo]) -> Result<()> {
+        let mut slope_changes = slope_changes.iter();
+        while self.ve_checkpoint < now {
+            let week = (self.ve_checkpoint / Self::WEEK + 1) * Self::WEEK;
+            if week > now {
+                self.decay_to(now)?;
+                break;
+            }
+            let Some(info) = slope_changes.next() else {
+                break;
+            };
+            require_keys_eq!(
+                info.key(),
+                VeSlopeChange::address(week),
+                ErrorCode::InvalidParameter
+            );
+            self.decay_to(week)?;
+            if !info.data_is_empty() {
+                require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidParameter);
+                let change = VeSlopeChange::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+                self.ve_slope = self.ve_slope.saturating_sub(change.slope);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails unless the aggregate voting power has been brought forward to `now`
+    pub fn require_current(&self, now: i64) -> Result<()> {
+        require!(self.ve_checkpoint >= now, ErrorCode::GovernanceCheckpointStale);
+        Ok(())
+    }
+
+    fn decay_to(&mut self, time: i64) -> Result<()> {
+        let elapsed = (time - self.ve_checkpoint) as u128;
+        let decay = self.ve_slope.checked_mul(elapsed).ok_or(ErrorCode::Overflow)?;
+        self.ve_bias = self.ve_bias.saturating_sub(decay);
+        self.ve_checkpoint = time;
+        Ok(())
+    }
+
+    /// Adds `amount` locked from `now` until `lock_end` to the aggregate
+    fn add_lock(&mut self, amount: u64, now: i64, lock_end: i64) -> Result<()> {
+        let contribution = (amount as u128)
+            .checked_mul((lock_end - now) as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        self.ve_bias = self.ve_bias.checked_add(contribution).ok_or(ErrorCode::Overflow)?;
+        self.ve_slope = self.ve_slope
+            .checked_add(amount as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Escrowed amount of the locks ending at one week boundary; the aggregate
+/// slope drops by `slope` when a checkpoint crosses `week`
+#[account]
+#[derive(Default)]
+pub struct VeSlopeChange {
+    /// Week boundary the locks end at
+    pub week: i64,
+
+    /// Escrowed amount under locks ending at `week`
+    pub slope: u128,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl VeSlopeChange {
+    pub const LEN: usize = 8 + // discriminator
+                          8 + // week
+                          16 + // slope
+                          1; // bump
+
+    pub fn address(week: i64) -> Pubkey {
+        Pubkey::find_program_address(&[b"ve_slope_change", &week.to_le_bytes()], &crate::ID).0
+    }
+}
+
+/// Governance tokens a voter has escrowed for voting power
+#[account]
+#[derive(Default)]
+pub struct VoterEscrow {
+    /// Voter owning the escrow
+    pub voter: Pubkey,
+
+    /// Token account holding the escrowed tokens
+    pub vault: Pubkey,
+
+    /// Tokens escrowed
+    pub amount: u64,
+
+    /// Tokens cannot be withdrawn before this time (set by voting)
+    pub locked_until: i64,
+
+    /// End of the voter-chosen lock; voting power decays to zero here
+    pub lock_end: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl VoterEscrow {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // voter
+                          32 + // vault
+                          8 + // amount
+                          8 + // locked_until
+                          8 + // lock_end
+                          1; // bump
+
+    /// Longest lock a voter can choose (4 years)
+    pub const MAX_LOCK_DURATION: i64 = 4 * 365 * 24 * 60 * 60;
+
+    /// Time-weighted voting power: the full escrowed amount at a maximum
+    /// length lock, decaying linearly to zero at `lock_end`
+    pub fn voting_power(&self, now: i64) -> Result<u64> {
+        if now >= self.lock_end {
+            return Ok(0);
+        }
+        let remaining = (self.lock_end - now).min(Self::MAX_LOCK_DURATION);
+        let power = (self.amount as u128)
+            .checked_mul(remaining as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / Self::MAX_LOCK_DURATION as u128;
+        Ok(power as u64)
+    }
+}
+
 #[derive(Accounts)]
 pub struct CreateProposal<'info> {
     #[account(mut)]
     pub proposer: Signer<'info>,
     
+    /// Slope change PDAs for any week boundaries since the last checkpoint are
+    /// passed as remaining accounts
+    #[account(
+        mut,
+        seeds = [b"governance_config"],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        seeds = [b"voter_escrow", proposer.key().as_ref()],
+        bump = voter_escrow.bump,
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+    
     #[account(init, payer = proposer, space = 8 + Proposal::BASE_LEN + Proposal::MAX_TITLE_LEN + Proposal::MAX_DESCRIPTION_LEN + Proposal::MAX_PARAMETER_KEY_LEN)]
     pub proposal: Account<'info, Proposal>,
     
@@ -77,11 +338,20 @@ pub struct CastVote<'info> {
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     
+    #[account(
+        mut,
+        seeds = [b"voter_escrow", voter.key().as_ref()],
+        bump = voter_escrow.bump,
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+    
+    // One vote per voter per proposal; a second attempt fails on init
     #[account(
         init,
         payer = voter,
         space = Vote::LEN,
-        constraint = !vote.exists() @ ErrorCode::AlreadyVoted,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
     )]
     pub vote: Account<'info, Vote>,
     
@@ -105,6 +375,183 @@ pub struct ExecuteProposal<'info> {
     pub program_state: Account<'info, ProgramState>,
 }
 
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == ProposalStatus::Active @ ErrorCode::InvalidParameter,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = GovernanceConfig::LEN,
+        seeds = [b"governance_config"],
+        bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGovernanceConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_config"],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DepositGovernanceTokens<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_config"],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    #[account(constraint = governance_mint.key() == governance_config.governance_mint @ ErrorCode::InvalidParameter)]
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoterEscrow::LEN,
+        seeds = [b"voter_escrow", voter.key().as_ref()],
+        bump,
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        seeds = [b"voter_escrow_vault", voter.key().as_ref()],
+        bump,
+        token::mint = governance_mint,
+        token::authority = voter_escrow,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    /// Slope change for the escrow's current lock end; required while the lock is running
+    #[account(
+        mut,
+        seeds = [b"ve_slope_change", voter_escrow.lock_end.to_le_bytes().as_ref()],
+        bump = lock_slope_change.bump,
+    )]
+    pub lock_slope_change: Option<Account<'info, VeSlopeChange>>,
+
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key() @ ErrorCode::Unauthorized,
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(lock_end: i64)]
+pub struct ExtendGovernanceLock<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_config"],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_escrow", voter.key().as_ref()],
+        bump = voter_escrow.bump,
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+
+    /// Slope change for the current lock end; required while the lock is running
+    #[account(
+        mut,
+        seeds = [b"ve_slope_change", voter_escrow.lock_end.to_le_bytes().as_ref()],
+        bump = old_slope_change.bump,
+    )]
+    pub old_slope_change: Option<Account<'info, VeSlopeChange>>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VeSlopeChange::LEN,
+        seeds = [b"ve_slope_change", lock_end.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub new_slope_change: Account<'info, VeSlopeChange>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CheckpointGovernance<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance_config"],
+        bump = governance_config.bump,
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawGovernanceTokens<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_escrow", voter.key().as_ref()],
+        bump = voter_escrow.bump,
+    )]
+    pub voter_escrow: Account<'info, VoterEscrow>,
+
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == voter_escrow.vault @ ErrorCode::InvalidParameter,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key() @ ErrorCode::Unauthorized,
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 pub fn create_proposal(
     ctx: Context<CreateProposal>,
     title: String,
@@ -125,16 +572,48 @@ pub fn create_proposal(
         parameter_key.len() <= Proposal::MAX_PARAMETER_KEY_LEN,
         ErrorCode::ParameterKeyTooLong
     );
+    require!(
+        (0..=Proposal::MAX_EXECUTION_DELAY).contains(&execution_delay),
+        ErrorCode::InvalidParameter
+    );
     
+    let governance_config = &mut ctx.accounts.governance_config;
     let proposal = &mut ctx.accounts.proposal;
     let clock = Clock::get()?;
     
+    governance_config.checkpoint(clock.unix_timestamp, ctx.remaining_accounts)?;
+    governance_config.require_current(clock.unix_timestamp)?;
+    
+    require!(
+        ctx.accounts.voter_escrow.voting_power(clock.unix_timestamp)?
+            >= governance_config.proposer_threshold,
+        ErrorCode::BelowProposerThreshold
+    );
+    
+    // Quorum is fixed against the aggregate voting power when the proposal is created
+    let quorum_votes = (governance_config.total_voting_power()? as u128)
+        .checked_mul(governance_config.quorum as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / 10_000;
+    
     proposal.proposer = ctx.accounts.proposer.key();
     proposal.title = title;
     proposal.description = description;
-    proposal.execution_time = clock.unix_timestamp + execution_delay;
+    proposal.voting_start = clock.unix_timestamp
+        .checked_add(governance_config.voting_delay)
+        .ok_or(ErrorCode::Overflow)?;
+    proposal.voting_end = proposal.voting_start
+        .checked_add(governance_config.voting_period)
+        .ok_or(ErrorCode::Overflow)?;
+    proposal.execution_time = proposal.voting_end
+        .checked_add(execution_delay)
+        .ok_or(ErrorCode::Overflow)?;
+    proposal.execution_deadline = proposal.execution_time
+        .checked_add(governance_config.execution_grace_period)
+        .ok_or(ErrorCode::Overflow)?;
     proposal.yes_votes = 0;
     proposal.no_votes = 0;
+    proposal.quorum_votes = quorum_votes as u64;
     proposal.status = ProposalStatus::Active;
     proposal.parameter_key = parameter_key;
     proposal.new_value = new_value;
@@ -142,14 +621,28 @@ pub fn create_proposal(
     Ok(())
 }
 
-pub fn cast_vote(
-    ctx: Context<CastVote>,
-    amount: u64,
-    support: bool,
-) -> Result<()> {
+pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
+    let voter_escrow = &mut ctx.accounts.voter_escrow;
     let vote = &mut ctx.accounts.vote;
     
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(
+        proposal.status == ProposalStatus::Active,
+        ErrorCode::InvalidParameter
+    );
+    require!(
+        now >= proposal.voting_start && now < proposal.voting_end,
+        ErrorCode::VotingClosed
+    );
+    
+    // Voting power comes from the escrow lock, and the tokens stay locked
+    // until voting ends so they cannot be moved and voted again
+    let amount = voter_escrow.voting_power(now)?;
+    require!(amount > 0, ErrorCode::NoVotingPower);
+    voter_escrow.locked_until = voter_escrow.locked_until.max(proposal.voting_end);
+    
     vote.voter = ctx.accounts.voter.key();
     vote.proposal = proposal.key();
     vote.amount = amount;
@@ -171,9 +664,10 @@ pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
     let program_state = &mut ctx.accounts.program_state;
     
     require!(
-        proposal.yes_votes > proposal.no_votes,
-        ErrorCode::InsufficientVotes
+        Clock::get()?.unix_timestamp <= proposal.execution_deadline,
+        ErrorCode::ProposalExpired
     );
+    require!(proposal.passed()?, ErrorCode::InsufficientVotes);
     
     match proposal.parameter_key.as_str() {
         "min_loan_amount" => program_state.min_loan_amount = proposal.new_value,
@@ -190,3 +684,282 @@ pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
     
     Ok(())
 }
+
+/// Closes out a proposal after voting: Rejected if it failed, Expired if it
+/// passed but was not executed within the grace period
+pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(now >= proposal.voting_end, ErrorCode::ProposalNotReady);
+    
+    if !proposal.passed()? {
+        proposal.status = ProposalStatus::Rejected;
+    } else if now > proposal.execution_deadline {
+        proposal.status = ProposalStatus::Expired;
+    } else {
+        return Err(ErrorCode::ProposalNotReady.into());
+    }
+    
+    Ok(())
+}
+
+pub fn initialize_governance(
+    ctx: Context<InitializeGovernance>,
+    voting_delay: i64,
+    voting_period: i64,
+    quorum: u64,
+    proposer_threshold: u64,
+    execution_grace_period: i64,
+) -> Result<()> {
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.authority = ctx.accounts.authority.key();
+    governance_config.governance_mint = ctx.accounts.governance_mint.key();
+    governance_config.ve_bias = 0;
+    governance_config.ve_slope = 0;
+    governance_config.ve_checkpoint = Clock::get()?.unix_timestamp;
+    governance_config.set_params(
+        voting_delay,
+        voting_period,
+        quorum,
+        proposer_threshold,
+        execution_grace_period,
+    )?;
+    governance_config.bump = ctx.bumps.governance_config;
+    Ok(())
+}
+
+pub fn update_governance_config(
+    ctx: Context<UpdateGovernanceConfig>,
+    voting_delay: i64,
+    voting_period: i64,
+    quorum: u64,
+    proposer_threshold: u64,
+    execution_grace_period: i64,
+) -> Result<()> {
+    ctx.accounts.governance_config.set_params(
+        voting_delay,
+        voting_period,
+        quorum,
+        proposer_threshold,
+        execution_grace_period,
+    )
+}
+
+/// Deposits governance tokens, increasing the amount under the existing lock.
+/// Slope change PDAs for any week boundaries since the last checkpoint are
+/// passed as remaining accounts.
+pub fn deposit_governance_tokens(
+    ctx: Context<DepositGovernanceTokens>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidParameter);
+    let now = Clock::get()?.unix_timestamp;
+
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.checkpoint(now, ctx.remaining_accounts)?;
+    governance_config.require_current(now)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                to: ctx.accounts.escrow_vault.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let voter_escrow = &mut ctx.accounts.voter_escrow;
+    voter_escrow.voter = ctx.accounts.voter.key();
+    voter_escrow.vault = ctx.accounts.escrow_vault.key();
+    voter_escrow.amount = voter_escrow.amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    voter_escrow.bump = ctx.bumps.voter_escrow;
+
+    // Tokens added under a running lock gain power right away; otherwise they
+    // count once the voter sets a lock with `extend_governance_lock`
+    if voter_escrow.lock_end > now {
+        let lock_slope_change = ctx.accounts.lock_slope_change
+            .as_mut()
+            .ok_or(ErrorCode::InvalidParameter)?;
+        lock_slope_change.slope = lock_slope_change.slope
+            .checked_add(amount as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        governance_config.add_lock(amount, now, voter_escrow.lock_end)?;
+    }
+
+    Ok(())
+}
+
+/// Extends the escrow lock to the week boundary `lock_end`, restoring voting
+/// power lost to decay. Slope change PDAs for any week boundaries since the
+/// last checkpoint are passed as remaining accounts.
+pub fn extend_governance_lock(ctx: Context<ExtendGovernanceLock>, lock_end: i64) -> Result<()> {
+    let voter_escrow = &mut ctx.accounts.voter_escrow;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        lock_end > voter_escrow.lock_end
+            && lock_end > now
+            && lock_end - now <= VoterEscrow::MAX_LOCK_DURATION
+            && lock_end % GovernanceConfig::WEEK == 0,
+        ErrorCode::InvalidLockEnd
+    );
+
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.checkpoint(now, ctx.remaining_accounts)?;
+    governance_config.require_current(now)?;
+
+    let amount = voter_escrow.amount;
+    if voter_escrow.lock_end > now {
+        // Move the running lock's slope to the new end and add the extra time
+        let old_slope_change = ctx.accounts.old_slope_change
+            .as_mut()
+            .ok_or(ErrorCode::InvalidParameter)?;
+        old_slope_change.slope = old_slope_change.slope.saturating_sub(amount as u128);
+        let extension = (amount as u128)
+            .checked_mul((lock_end - voter_escrow.lock_end) as u128)
+            .ok_or(ErrorCode::Overflow)?;
+        governance_config.ve_bias = governance_config.ve_bias
+            .checked_add(extension)
+            .ok_or(ErrorCode::Overflow)?;
+    } else {
+        // An expired or unset lock already stopped counting at its old end
+        governance_config.add_lock(amount, now, lock_end)?;
+    }
+
+    let new_slope_change = &mut ctx.accounts.new_slope_change;
+    new_slope_change.week = lock_end;
+    new_slope_change.slope = new_slope_change.slope
+        .checked_add(amount as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    new_slope_change.bump = ctx.bumps.new_slope_change;
+
+    voter_escrow.lock_end = lock_end;
+    Ok(())
+}
+
+/// Brings the aggregate voting power forward; slope change PDAs for the week
+/// boundaries to cross are passed as remaining accounts
+pub fn checkpoint_governance(ctx: Context<CheckpointGovernance>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.governance_config.checkpoint(now, ctx.remaining_accounts)
+}
+
+pub fn withdraw_governance_tokens(
+    ctx: Context<WithdrawGovernanceTokens>,
+    amount: u64,
+) -> Result<()> {
+    let voter_escrow = &mut ctx.accounts.voter_escrow;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= voter_escrow.locked_until && now >= voter_escrow.lock_end,
+        ErrorCode::GovernanceTokensLocked
+    );
+    require!(
+        amount > 0 && amount <= voter_escrow.amount,
+        ErrorCode::InvalidParameter
+    );
+
+    let voter_key = voter_escrow.voter;
+    let seeds = &[
+        b"voter_escrow".as_ref(),
+        voter_key.as_ref(),
+        &[voter_escrow.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.escrow_vault.to_account_info(),
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: voter_escrow.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    // The lock has ended, so its slope was already removed from the aggregate
+    voter_escrow.amount -= amount;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow(amount: u64, lock_end: i64) -> VoterEscrow {
+        VoterEscrow {
+            amount,
+            lock_end,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn voting_power_is_full_at_maximum_lock() {
+        let escrow = escrow(1_000, VoterEscrow::MAX_LOCK_DURATION);
+        assert_eq!(escrow.voting_power(0).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn voting_power_decays_linearly() {
+        let escrow = escrow(1_000, VoterEscrow::MAX_LOCK_DURATION);
+        assert_eq!(escrow.voting_power(VoterEscrow::MAX_LOCK_DURATION / 2).unwrap(), 500);
+        assert_eq!(escrow.voting_power(VoterEscrow::MAX_LOCK_DURATION * 3 / 4).unwrap(), 250);
+    }
+
+    #[test]
+    fn voting_power_is_zero_once_unlocked() {
+        let escrow = escrow(1_000, 100);
+        assert_eq!(escrow.voting_power(100).unwrap(), 0);
+        assert_eq!(escrow.voting_power(200).unwrap(), 0);
+    }
+
+    #[test]
+    fn aggregate_power_matches_escrow_power_between_boundaries() {
+        let mut config = GovernanceConfig::default();
+        config.add_lock(1_000, 0, VoterEscrow::MAX_LOCK_DURATION).unwrap();
+        config.add_lock(500, 0, VoterEscrow::MAX_LOCK_DURATION / 2).unwrap();
+
+        let now = GovernanceConfig::WEEK - 1;
+        config.checkpoint(now, &[]).unwrap();
+        let expected = escrow(1_000, VoterEscrow::MAX_LOCK_DURATION).voting_power(now).unwrap()
+            + escrow(500, VoterEscrow::MAX_LOCK_DURATION / 2).voting_power(now).unwrap();
+        assert!(config.total_voting_power().unwrap().abs_diff(expected) <= 1);
+    }
+
+    #[test]
+    fn checkpoint_stops_at_uncovered_week_boundary() {
+        let mut config = GovernanceConfig::default();
+        config.add_lock(1_000, 0, VoterEscrow::MAX_LOCK_DURATION).unwrap();
+
+        config.checkpoint(GovernanceConfig::WEEK + 1, &[]).unwrap();
+        assert_eq!(config.ve_checkpoint, 0);
+        assert_eq!(
+            config.require_current(GovernanceConfig::WEEK + 1).unwrap_err(),
+            ErrorCode::GovernanceCheckpointStale.into()
+        );
+    }
+
+    #[test]
+    fn set_params_rejects_quorum_below_minimum() {
+        let mut config = GovernanceConfig::default();
+        assert!(config.set_params(0, 1, 0, 0, 1).is_err());
+        assert!(config.set_params(0, 1, GovernanceConfig::MIN_QUORUM - 1, 0, 1).is_err());
+        assert!(config.set_params(0, 1, GovernanceConfig::MIN_QUORUM, 0, 1).is_ok());
+    }
+
+    #[test]
+    fn voting_power_caps_remaining_lock_at_maximum() {
+        let escrow = escrow(1_000, 2 * VoterEscrow::MAX_LOCK_DURATION);
+        assert_eq!(escrow.voting_power(0).unwrap(), 1_000);
+    }
+}
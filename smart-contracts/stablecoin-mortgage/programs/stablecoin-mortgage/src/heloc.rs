@@ -0,0 +1,835 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_mint::{freeze_property_nft, thaw_property_nft};
+use crate::property_records::PropertyRecord;
+use crate::risk_assessment::RiskAssessment;
+use crate::state::*;
+use crate::tranches::apply_tranche_waterfall;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+
+/// Home equity line of credit secured by a property NFT
+#[account]
+#[derive(Default)]
+pub struct Heloc {
+    /// Borrower wallet
+    pub borrower: Pubkey,
+
+    /// Lending pool providing the credit line
+    pub lending_pool: Pubkey,
+
+    /// Property NFT securing the line
+    pub property_nft: Pubkey,
+
+    /// Maximum drawn balance, derived from the appraised value
+    pub credit_limit: u64,
+
+    /// Principal currently drawn
+    pub drawn_balance: u64,
+
+    /// Drawn principal matched against the pool's committed term liquidity
+    pub term_matched: u64,
+
+    /// Interest accrued on the drawn balance and not yet repaid
+    pub accrued_interest: u64,
+
+    /// Interest rate (basis points)
+    pub interest_rate: u64,
+
+    /// End of the draw period
+    pub draw_period_end: i64,
+
+    /// Length of the amortizing repayment period in seconds
+    pub repayment_duration: u64,
+
+    /// Draw plus repayment period in seconds, used to match term liquidity
+    pub loan_duration: u64,
+
+    /// Monthly payment once in the repayment period
+    pub monthly_payment: u64,
+
+    /// Next payment due timestamp (repayment period only)
+    pub next_payment_due: i64,
+
+    /// Timestamp interest was last accrued to
+    pub last_accrual: i64,
+
+    /// Current phase of the line
+    pub status: HelocStatus,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub enum HelocStatus {
+    #[default]
+    Draw,
+    Repayment,
+    Closed,
+}
+
+impl Heloc {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // borrower
+                          32 + // lending_pool
+                          32 + // property_nft
+                          8 + // credit_limit
+                          8 + // drawn_balance
+                          8 + // term_matched
+                          8 + // accrued_interest
+                          8 + // interest_rate
+                          8 + // draw_period_end
+                          8 + // repayment_duration
+                          8 + // loan_duration
+                          8 + // monthly_payment
+                          8 + // next_payment_due
+                          8 + // last_accrual
+                          1 + // status
+                          1; // bump
+
+    /// Credit limit as a share of the appraised value (basis points)
+    pub const MAX_LTV: u64 = 8_000;
+
+    fn accrue(&mut self, now: i64) -> Result<()> {
+        let interest = Mortgage::interest_for(
+            self.drawn_balance,
+            self.interest_rate,
+            now - self.last_accrual,
+        )?;
+        self.accrued_interest = self.accrued_interest
+            .checked_add(interest)
+            .ok_or(ErrorCode::Overflow)?;
+        self.last_accrual = now;
+        Ok(())
+    }
+
+    /// Releases the term liquidity backing `principal` repaid or written off.
+    /// Capitalized interest was never matched, so only matched principal is freed.
+    fn release_term(&mut self, lending_pool: &mut LendingPool, principal: u64) {
+        let released = principal.min(self.term_matched);
+        self.term_matched -= released;
+        lending_pool.release_term_liquidity(released, self.loan_duration);
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenHeloc<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(constraint = lending_pool.is_active @ ErrorCode::PoolInactive)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: borrower KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == borrower.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = !property_nft.is_locked @ ErrorCode::NFTAlreadyLocked,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    #[account(
+        seeds = [b"risk_assessment", property_nft.key().as_ref()],
+        bump = risk_assessment.bump,
+        constraint = risk_assessment.is_valid @ ErrorCode::InvalidAssessment,
+    )]
+    pub risk_assessment: Account<'info, RiskAssessment>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = Heloc::LEN,
+        seeds = [b"heloc", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub heloc: Account<'info, Heloc>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct HelocDraw<'info> {
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"heloc", heloc.property_nft.as_ref()],
+        bump = heloc.bump,
+        constraint = heloc.borrower == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub heloc: Account<'info, Heloc>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == heloc.property_nft @ ErrorCode::InvalidParameter,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == heloc.lending_pool @ ErrorCode::InvalidParameter,
+        constraint = lending_pool.is_active @ ErrorCode::PoolInactive,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct HelocRepay<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"heloc", heloc.property_nft.as_ref()],
+        bump = heloc.bump,
+    )]
+    pub heloc: Account<'info, Heloc>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == heloc.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", heloc.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertHeloc<'info> {
+    #[account(
+        mut,
+        seeds = [b"heloc", heloc.property_nft.as_ref()],
+        bump = heloc.bump,
+    )]
+    pub heloc: Account<'info, Heloc>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == heloc.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", heloc.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+}
+
+#[derive(Accounts)]
+pub struct LiquidateHeloc<'info> {
+    pub authority: Signer<'info>,
+
+    /// CHECK: borrower receiving the closed line's rent
+    #[account(mut, address = heloc.borrower @ ErrorCode::InvalidParameter)]
+    pub borrower: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"heloc", property_nft.key().as_ref()],
+        bump = heloc.bump,
+        constraint = heloc.status == HelocStatus::Repayment @ ErrorCode::MortgageInactive,
+    )]
+    pub heloc: Account<'info, Heloc>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.key() == heloc.lending_pool @ ErrorCode::InvalidParameter,
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool tranche accounting PDA; uninitialized for untranched pools
+    #[account(mut)]
+    pub pool_tranches: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", heloc.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(heloc.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    /// Account holding the sale proceeds of the foreclosed property
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Property owner's account receiving the surplus after the line is repaid
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == property_nft.owner @ ErrorCode::InvalidNFTOwner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseHeloc<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        close = borrower,
+        seeds = [b"heloc", property_nft.key().as_ref()],
+        bump = heloc.bump,
+        constraint = heloc.borrower == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub heloc: Account<'info, Heloc>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.locked_by == Some(heloc.key()) @ ErrorCode::NFTNotLocked,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn open_heloc(
+    ctx: Context<OpenHeloc>,
+    draw_period: i64,
+    repayment_duration: u64,
+) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.borrower.key(),
+        PoolAccess::Borrow,
+    )?;
+    let borrower_jurisdiction = enforce_borrower_identity(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &ctx.accounts.borrower.key(),
+    )?;
+
+    require!(draw_period > 0, ErrorCode::InvalidLoanDuration);
+    require!(
+        repayment_duration >= Mortgage::PAYMENT_INTERVAL as u64,
+        ErrorCode::InvalidLoanDuration
+    );
+
+    let heloc = &mut ctx.accounts.heloc;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let clock = Clock::get()?;
+
+    let credit_limit = (ctx.accounts.risk_assessment.appraised_value as u128)
+        .checked_mul(Heloc::MAX_LTV as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / 10_000;
+    let credit_limit = u64::try_from(credit_limit).map_err(|_| ErrorCode::Overflow)?;
+    ctx.accounts.jurisdiction_rule.check_loan(
+        LoanProduct::Heloc,
+        ctx.accounts.lending_pool.interest_rate,
+        credit_limit,
+        ctx.accounts.risk_assessment.appraised_value,
+        borrower_jurisdiction,
+    )?;
+
+    heloc.borrower = ctx.accounts.borrower.key();
+    heloc.lending_pool = ctx.accounts.lending_pool.key();
+    heloc.property_nft = property_nft.key();
+    heloc.credit_limit = credit_limit;
+    heloc.drawn_balance = 0;
+    heloc.term_matched = 0;
+    heloc.accrued_interest = 0;
+    heloc.interest_rate = ctx.accounts.lending_pool.interest_rate;
+    heloc.draw_period_end = clock.unix_timestamp
+        .checked_add(draw_period)
+        .ok_or(ErrorCode::Overflow)?;
+    heloc.repayment_duration = repayment_duration;
+    heloc.loan_duration = (draw_period as u64)
+        .checked_add(repayment_duration)
+        .ok_or(ErrorCode::Overflow)?;
+    heloc.monthly_payment = 0;
+    heloc.next_payment_due = 0;
+    heloc.last_accrual = clock.unix_timestamp;
+    heloc.status = HelocStatus::Draw;
+    heloc.bump = ctx.bumps.heloc;
+
+    property_nft.is_locked = true;
+    property_nft.locked_by = Some(heloc.key());
+
+    freeze_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &ctx.accounts.borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    Ok(())
+}
+
+pub fn heloc_draw(ctx: Context<HelocDraw>, amount: u64) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.borrower.key(),
+        PoolAccess::Borrow,
+    )?;
+
+    let heloc = &mut ctx.accounts.heloc;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, ErrorCode::InvalidParameter);
+    require!(
+        heloc.status == HelocStatus::Draw && clock.unix_timestamp < heloc.draw_period_end,
+        ErrorCode::DrawPeriodEnded
+    );
+
+    heloc.accrue(clock.unix_timestamp)?;
+
+    let new_balance = heloc.drawn_balance
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(new_balance <= heloc.credit_limit, ErrorCode::CreditLimitExceeded);
+    require!(
+        lending_pool.available_liquidity() >= amount,
+        ErrorCode::InsufficientLiquidity
+    );
+    enforce_loan_limits(
+        lending_pool,
+        &ctx.accounts.pool_limits,
+        &ctx.accounts.borrower_exposure,
+        new_balance,
+        amount,
+    )?;
+    lending_pool.match_term_liquidity(amount, heloc.loan_duration)?;
+
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        lending_pool.authority.as_ref(),
+        &[lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stablecoin_vault.to_account_info(),
+                to: ctx.accounts.borrower_token_account.to_account_info(),
+                authority: lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    heloc.drawn_balance = new_balance;
+    heloc.term_matched = heloc.term_matched
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    ctx.accounts.borrower_exposure.add(amount)?;
+    lending_pool.total_borrowed = lending_pool.total_borrowed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn heloc_repay(ctx: Context<HelocRepay>, amount: u64) -> Result<()> {
+    let heloc = &mut ctx.accounts.heloc;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let clock = Clock::get()?;
+
+    require!(heloc.status != HelocStatus::Closed, ErrorCode::MortgageInactive);
+    require!(amount > 0, ErrorCode::InsufficientPayment);
+
+    heloc.accrue(clock.unix_timestamp)?;
+
+    // Interest is settled before principal; overpayment is capped at what is owed
+    let interest_paid = amount.min(heloc.accrued_interest);
+    let principal_paid = (amount - interest_paid).min(heloc.drawn_balance);
+    let total_paid = interest_paid + principal_paid;
+    require!(total_paid > 0, ErrorCode::InsufficientPayment);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                to: ctx.accounts.stablecoin_vault.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        total_paid,
+    )?;
+
+    heloc.accrued_interest -= interest_paid;
+    heloc.drawn_balance -= principal_paid;
+    heloc.release_term(lending_pool, principal_paid);
+    ctx.accounts.borrower_exposure.sub(principal_paid);
+
+    if heloc.status == HelocStatus::Repayment
+        && (total_paid >= heloc.monthly_payment || heloc.drawn_balance == 0)
+    {
+        heloc.next_payment_due = heloc.next_payment_due
+            .checked_add(Mortgage::PAYMENT_INTERVAL)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(principal_paid);
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(interest_paid)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    apply_tranche_waterfall(
+        &lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        interest_paid,
+        0,
+    )?;
+
+    Ok(())
+}
+
+pub fn convert_heloc_to_repayment(ctx: Context<ConvertHeloc>) -> Result<()> {
+    let heloc = &mut ctx.accounts.heloc;
+    let clock = Clock::get()?;
+
+    require!(heloc.status == HelocStatus::Draw, ErrorCode::InvalidParameter);
+    require!(
+        clock.unix_timestamp >= heloc.draw_period_end,
+        ErrorCode::DrawPeriodActive
+    );
+
+    heloc.accrue(clock.unix_timestamp)?;
+
+    let months = heloc.repayment_duration / Mortgage::PAYMENT_INTERVAL as u64;
+    let principal = heloc.drawn_balance
+        .checked_add(heloc.accrued_interest)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // Unpaid interest is capitalized into the amortizing balance. The pool books it
+    // as accrued income: lent out and owed to depositors, so liquidity is unchanged.
+    let capitalized = heloc.accrued_interest;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    lending_pool.total_borrowed = lending_pool.total_borrowed
+        .checked_add(capitalized)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(capitalized)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+    apply_tranche_waterfall(&lending_pool.key(), &ctx.accounts.pool_tranches, capitalized, 0)?;
+    // Repayments reduce exposure by principal, which now includes the capitalized interest
+    ctx.accounts.borrower_exposure.add(capitalized)?;
+
+    heloc.drawn_balance = principal;
+    heloc.accrued_interest = 0;
+    heloc.monthly_payment = Mortgage::amortized_payment(principal, heloc.interest_rate, months)?;
+    heloc.next_payment_due = clock.unix_timestamp
+        .checked_add(Mortgage::PAYMENT_INTERVAL)
+        .ok_or(ErrorCode::Overflow)?;
+    heloc.status = HelocStatus::Repayment;
+
+    Ok(())
+}
+
+/// Settles a line whose repayment is overdue out of the foreclosure sale proceeds.
+/// The line is the property's only lien, so proceeds repay accrued interest and
+/// then principal; the pool writes off any shortfall and the owner gets the rest.
+pub fn liquidate_heloc(ctx: Context<LiquidateHeloc>, proceeds: u64) -> Result<()> {
+    let heloc = &mut ctx.accounts.heloc;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let clock = Clock::get()?;
+
+    require!(proceeds > 0, ErrorCode::InvalidParameter);
+    require!(
+        clock.unix_timestamp > heloc.next_payment_due,
+        ErrorCode::PaymentNotOverdue
+    );
+
+    heloc.accrue(clock.unix_timestamp)?;
+
+    let interest_paid = proceeds.min(heloc.accrued_interest);
+    let principal_paid = (proceeds - interest_paid).min(heloc.drawn_balance);
+    let total_paid = interest_paid + principal_paid;
+    let shortfall = heloc.drawn_balance - principal_paid;
+
+    if total_paid > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.stablecoin_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            total_paid,
+        )?;
+    }
+    let surplus = proceeds - total_paid;
+    if surplus > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            surplus,
+        )?;
+    }
+
+    let outstanding = heloc.drawn_balance;
+    heloc.release_term(lending_pool, outstanding);
+    ctx.accounts.borrower_exposure.sub(outstanding);
+    lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(outstanding);
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(interest_paid)
+        .ok_or(ErrorCode::Overflow)?
+        .saturating_sub(shortfall);
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    heloc.status = HelocStatus::Closed;
+
+    apply_tranche_waterfall(
+        &lending_pool.key(),
+        &ctx.accounts.pool_tranches,
+        interest_paid,
+        shortfall,
+    )?;
+
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &property_nft.owner,
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn close_heloc(ctx: Context<CloseHeloc>) -> Result<()> {
+    let heloc = &mut ctx.accounts.heloc;
+    let property_nft = &mut ctx.accounts.property_nft;
+
+    heloc.accrue(Clock::get()?.unix_timestamp)?;
+    require!(
+        heloc.drawn_balance == 0 && heloc.accrued_interest == 0,
+        ErrorCode::LienOutstanding
+    );
+
+    heloc.status = HelocStatus::Closed;
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &ctx.accounts.borrower.key(),
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )?;
+
+    Ok(())
+}
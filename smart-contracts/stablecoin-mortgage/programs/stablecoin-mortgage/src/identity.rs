@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+/// KYC provider registered by the program authority
+#[account]
+#[derive(Default)]
+pub struct KycProvider {
+    /// Provider wallet that signs attestations
+    pub provider: Pubkey,
+
+    /// Program authority that registered the provider
+    pub registered_by: Pubkey,
+
+    /// False once the registration has been revoked
+    pub is_active: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl KycProvider {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // provider
+                          32 + // registered_by
+                          1 + // is_active
+                          1; // bump
+}
+
+/// KYC attestation for a borrower
+#[account]
+#[derive(Default)]
+pub struct BorrowerIdentity {
+    /// Attested borrower wallet
+    pub borrower: Pubkey,
+
+    /// KYC provider that issued the attestation
+    pub provider: Pubkey,
+
+    /// Verification level reached by the borrower
+    pub verification_level: u8,
+
+    /// Jurisdiction the borrower was verified in
+    pub jurisdiction: JurisdictionCode,
+
+    /// Hash of the off-chain KYC dossier
+    pub dossier_hash: [u8; 32],
+
+    /// Issue date
+    pub issued_at: i64,
+
+    /// Expiry date
+    pub expires_at: i64,
+
+    /// True once the provider has revoked the attestation
+    pub revoked: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl BorrowerIdentity {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // borrower
+                          32 + // provider
+                          1 + // verification_level
+                          8 + // jurisdiction
+                          32 + // dossier_hash
+                          8 + // issued_at
+                          8 + // expires_at
+                          1 + // revoked
+                          1; // bump
+}
+
+/// Enforces the pool's minimum KYC level for `borrower` and returns the jurisdiction
+/// the borrower was verified in, or `None` if the pool doesn't require verification.
+/// `borrower_identity` must be the borrower's `BorrowerIdentity` address and
+/// `identity_provider` the `KycProvider` that issued it.
+pub fn enforce_borrower_identity(
+    lending_pool: &LendingPool,
+    borrower_identity: &AccountInfo,
+    identity_provider: &AccountInfo,
+    borrower: &Pubkey,
+) -> Result<Option<JurisdictionCode>> {
+    if lending_pool.min_verification_level == 0 {
+        return Ok(None);
+    }
+
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"borrower_identity", borrower.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(borrower_identity.key(), expected, ErrorCode::IdentityMissing);
+    require!(!borrower_identity.data_is_empty(), ErrorCode::IdentityMissing);
+
+    let identity = Account::<BorrowerIdentity>::try_from(borrower_identity)?;
+    require!(!identity.revoked, ErrorCode::IdentityRevoked);
+
+    // Attestations lapse with their provider's registration
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"kyc_provider", identity.provider.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(identity_provider.key(), expected, ErrorCode::IdentityProviderInactive);
+    let provider = Account::<KycProvider>::try_from(identity_provider)?;
+    require!(
+        provider.is_active && provider.provider == identity.provider,
+        ErrorCode::IdentityProviderInactive
+    );
+    require!(
+        Clock::get()?.unix_timestamp < identity.expires_at,
+        ErrorCode::AttestationExpired
+    );
+    require!(
+        identity.verification_level >= lending_pool.min_verification_level,
+        ErrorCode::InsufficientVerificationLevel
+    );
+
+    Ok(Some(identity.jurisdiction))
+}
+
+#[derive(Accounts)]
+pub struct RegisterKycProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: wallet being registered as a KYC provider
+    pub provider_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = KycProvider::LEN,
+        seeds = [b"kyc_provider", provider_wallet.key().as_ref()],
+        bump,
+    )]
+    pub kyc_provider: Account<'info, KycProvider>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeKycProvider<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"kyc_provider", kyc_provider.provider.as_ref()],
+        bump = kyc_provider.bump,
+    )]
+    pub kyc_provider: Account<'info, KycProvider>,
+}
+
+#[derive(Accounts)]
+pub struct IssueBorrowerIdentity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    #[account(
+        seeds = [b"kyc_provider", provider.key().as_ref()],
+        bump = kyc_provider.bump,
+        constraint = kyc_provider.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub kyc_provider: Account<'info, KycProvider>,
+
+    /// Program authority; co-signs when another provider re-issues an attestation
+    pub authority: Option<Signer<'info>>,
+
+    #[account(
+        constraint = authority.as_ref().map_or(true, |authority| {
+            authority.key() == program_state.authority
+        }) @ ErrorCode::Unauthorized,
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: borrower wallet being attested
+    pub borrower: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = BorrowerIdentity::LEN,
+        seeds = [b"borrower_identity", borrower.key().as_ref()],
+        bump,
+    )]
+    pub borrower_identity: Account<'info, BorrowerIdentity>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeBorrowerIdentity<'info> {
+    pub provider: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_identity", borrower_identity.borrower.as_ref()],
+        bump = borrower_identity.bump,
+        constraint = borrower_identity.provider == provider.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_identity: Account<'info, BorrowerIdentity>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolVerificationLevel<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+}
+
+pub fn register_kyc_provider(ctx: Context<RegisterKycProvider>) -> Result<()> {
+    let kyc_provider = &mut ctx.accounts.kyc_provider;
+    kyc_provider.provider = ctx.accounts.provider_wallet.key();
+    kyc_provider.registered_by = ctx.accounts.authority.key();
+    kyc_provider.is_active = true;
+    kyc_provider.bump = ctx.bumps.kyc_provider;
+    Ok(())
+}
+
+pub fn revoke_kyc_provider(ctx: Context<RevokeKycProvider>) -> Result<()> {
+    ctx.accounts.kyc_provider.is_active = false;
+    Ok(())
+}
+
+pub fn issue_borrower_identity(
+    ctx: Context<IssueBorrowerIdentity>,
+    verification_level: u8,
+    jurisdiction: JurisdictionCode,
+    dossier_hash: [u8; 32],
+    expires_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(verification_level > 0, ErrorCode::InvalidParameter);
+    require!(expires_at > clock.unix_timestamp, ErrorCode::InvalidParameter);
+
+    // Re-issuing replaces the previous attestation, including a revoked one. Only the
+    // issuing provider may do so unless the program authority hands it to another
+    let borrower_identity = &mut ctx.accounts.borrower_identity;
+    require!(
+        borrower_identity.provider == Pubkey::default()
+            || borrower_identity.provider == ctx.accounts.provider.key()
+            || ctx.accounts.authority.is_some(),
+        ErrorCode::Unauthorized
+    );
+    borrower_identity.borrower = ctx.accounts.borrower.key();
+    borrower_identity.provider = ctx.accounts.provider.key();
+    borrower_identity.verification_level = verification_level;
+    borrower_identity.jurisdiction = jurisdiction;
+    borrower_identity.dossier_hash = dossier_hash;
+    borrower_identity.issued_at = clock.unix_timestamp;
+    borrower_identity.expires_at = expires_at;
+    borrower_identity.revoked = false;
+    borrower_identity.bump = ctx.bumps.borrower_identity;
+
+    Ok(())
+}
+
+pub fn revoke_borrower_identity(ctx: Context<RevokeBorrowerIdentity>) -> Result<()> {
+    ctx.accounts.borrower_identity.revoked = true;
+    Ok(())
+}
+
+pub fn set_pool_verification_level(
+    ctx: Context<SetPoolVerificationLevel>,
+    min_verification_level: u8,
+) -> Result<()> {
+    ctx.accounts.lending_pool.min_verification_level = min_verification_level;
+    Ok(())
+}
@@ -8,6 +8,27 @@ mod errors;
 mod rewards;
 mod risk_assessment;
 mod governance;
+mod blanket_mortgage;
+mod lien_registry;
+mod heloc;
+mod construction_loan;
+mod closing_escrow;
+mod assumption;
+mod refinance;
+mod syndication;
+mod tranches;
+mod term_deposits;
+mod withdrawal_queue;
+mod pool_limits;
+mod allowlist;
+mod identity;
+mod compliance;
+mod property_mint;
+mod property_records;
+mod title_registry;
+mod fractions;
+mod compressed_properties;
+mod migrations;
 
 use state::*;
 use instructions::*;
@@ -15,6 +36,27 @@ use errors::*;
 use rewards::*;
 use risk_assessment::*;
 use governance::*;
+use blanket_mortgage::*;
+use lien_registry::*;
+use heloc::*;
+use construction_loan::*;
+use closing_escrow::*;
+use assumption::*;
+use refinance::*;
+use syndication::*;
+use tranches::*;
+use term_deposits::*;
+use withdrawal_queue::*;
+use pool_limits::*;
+use allowlist::*;
+use identity::*;
+use compliance::*;
+use property_mint::*;
+use property_records::*;
+use title_registry::*;
+use fractions::*;
+use compressed_properties::*;
+use migrations::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -88,14 +130,6 @@ pub mod stablecoin_mortgage {
     }
 
     // NFT collateral instructions
-    pub fn register_property_nft(
-        ctx: Context<RegisterPropertyNFT>,
-        property_value: u64,
-        property_address: String,
-    ) -> Result<()> {
-        instructions::nft::register_property_nft(ctx, property_value, property_address)
-    }
-
     pub fn lock_property_nft(ctx: Context<LockPropertyNFT>) -> Result<()> {
         instructions::nft::lock_property_nft(ctx)
     }
@@ -117,6 +151,14 @@ pub mod stablecoin_mortgage {
     }
 
     // Risk assessment instructions
+    pub fn register_assessor(ctx: Context<RegisterAssessor>) -> Result<()> {
+        risk_assessment::register_assessor(ctx)
+    }
+
+    pub fn revoke_assessor(ctx: Context<RevokeAssessor>) -> Result<()> {
+        risk_assessment::revoke_assessor(ctx)
+    }
+
     pub fn create_risk_assessment(
         ctx: Context<CreateRiskAssessment>,
         appraised_value: u64,
@@ -149,16 +191,618 @@ pub mod stablecoin_mortgage {
         governance::create_proposal(ctx, title, description, parameter_key, new_value, execution_delay)
     }
 
-    pub fn cast_vote(
-        ctx: Context<CastVote>,
-        amount: u64,
-        support: bool,
-    ) -> Result<()> {
-        governance::cast_vote(ctx, amount, support)
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        governance::cast_vote(ctx, support)
     }
 
     pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
         governance::execute_proposal(ctx)
     }
-}
 
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        governance::finalize_proposal(ctx)
+    }
+
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        voting_delay: i64,
+        voting_period: i64,
+        quorum: u64,
+        proposer_threshold: u64,
+        execution_grace_period: i64,
+    ) -> Result<()> {
+        governance::initialize_governance(
+            ctx,
+            voting_delay,
+            voting_period,
+            quorum,
+            proposer_threshold,
+            execution_grace_period,
+        )
+    }
+
+    pub fn update_governance_config(
+        ctx: Context<UpdateGovernanceConfig>,
+        voting_delay: i64,
+        voting_period: i64,
+        quorum: u64,
+        proposer_threshold: u64,
+        execution_grace_period: i64,
+    ) -> Result<()> {
+        governance::update_governance_config(
+            ctx,
+            voting_delay,
+            voting_period,
+            quorum,
+            proposer_threshold,
+            execution_grace_period,
+        )
+    }
+
+    pub fn deposit_governance_tokens(
+        ctx: Context<DepositGovernanceTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        governance::deposit_governance_tokens(ctx, amount)
+    }
+
+    pub fn extend_governance_lock(
+        ctx: Context<ExtendGovernanceLock>,
+        lock_end: i64,
+    ) -> Result<()> {
+        governance::extend_governance_lock(ctx, lock_end)
+    }
+
+    pub fn checkpoint_governance(ctx: Context<CheckpointGovernance>) -> Result<()> {
+        governance::checkpoint_governance(ctx)
+    }
+
+    pub fn withdraw_governance_tokens(
+        ctx: Context<WithdrawGovernanceTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        governance::withdraw_governance_tokens(ctx, amount)
+    }
+
+    // Blanket mortgage instructions
+    pub fn create_blanket_collateral(ctx: Context<CreateBlanketCollateral>) -> Result<()> {
+        blanket_mortgage::create_blanket_collateral(ctx)
+    }
+
+    pub fn add_blanket_property(ctx: Context<AddBlanketProperty>) -> Result<()> {
+        blanket_mortgage::add_blanket_property(ctx)
+    }
+
+    pub fn release_blanket_property(ctx: Context<ReleaseBlanketProperty>) -> Result<()> {
+        blanket_mortgage::release_blanket_property(ctx)
+    }
+
+    pub fn allocate_blanket_proceeds(
+        ctx: Context<AllocateBlanketProceeds>,
+        proceeds: u64,
+    ) -> Result<()> {
+        blanket_mortgage::allocate_blanket_proceeds(ctx, proceeds)
+    }
+
+    pub fn release_seized_blanket_property(
+        ctx: Context<ReleaseSeizedBlanketProperty>,
+    ) -> Result<()> {
+        blanket_mortgage::release_seized_blanket_property(ctx)
+    }
+
+    // Lien registry instructions
+    pub fn initialize_lien_registry(ctx: Context<InitializeLienRegistry>) -> Result<()> {
+        lien_registry::initialize_lien_registry(ctx)
+    }
+
+    pub fn record_lien(ctx: Context<RecordLien>) -> Result<()> {
+        lien_registry::record_lien(ctx)
+    }
+
+    pub fn release_lien(ctx: Context<ReleaseLien>) -> Result<()> {
+        lien_registry::release_lien(ctx)
+    }
+
+    pub fn refresh_lien(ctx: Context<RefreshLien>) -> Result<()> {
+        lien_registry::refresh_lien(ctx)
+    }
+
+    pub fn distribute_lien_proceeds<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeLienProceeds<'info>>,
+        proceeds: u64,
+    ) -> Result<()> {
+        lien_registry::distribute_lien_proceeds(ctx, proceeds)
+    }
+
+    // HELOC instructions
+    pub fn open_heloc(
+        ctx: Context<OpenHeloc>,
+        draw_period: i64,
+        repayment_duration: u64,
+    ) -> Result<()> {
+        heloc::open_heloc(ctx, draw_period, repayment_duration)
+    }
+
+    pub fn heloc_draw(ctx: Context<HelocDraw>, amount: u64) -> Result<()> {
+        heloc::heloc_draw(ctx, amount)
+    }
+
+    pub fn heloc_repay(ctx: Context<HelocRepay>, amount: u64) -> Result<()> {
+        heloc::heloc_repay(ctx, amount)
+    }
+
+    pub fn convert_heloc_to_repayment(ctx: Context<ConvertHeloc>) -> Result<()> {
+        heloc::convert_heloc_to_repayment(ctx)
+    }
+
+    pub fn liquidate_heloc(ctx: Context<LiquidateHeloc>, proceeds: u64) -> Result<()> {
+        heloc::liquidate_heloc(ctx, proceeds)
+    }
+
+    pub fn close_heloc(ctx: Context<CloseHeloc>) -> Result<()> {
+        heloc::close_heloc(ctx)
+    }
+
+    // Construction loan instructions
+    pub fn register_inspector(ctx: Context<RegisterInspector>) -> Result<()> {
+        construction_loan::register_inspector(ctx)
+    }
+
+    pub fn revoke_inspector(ctx: Context<RevokeInspector>) -> Result<()> {
+        construction_loan::revoke_inspector(ctx)
+    }
+
+    pub fn create_construction_loan(
+        ctx: Context<CreateConstructionLoan>,
+        milestone_amounts: Vec<u64>,
+        loan_duration: u64,
+        construction_period: i64,
+    ) -> Result<()> {
+        construction_loan::create_construction_loan(
+            ctx,
+            milestone_amounts,
+            loan_duration,
+            construction_period,
+        )
+    }
+
+    pub fn attest_milestone(ctx: Context<AttestMilestone>, milestone_index: u8) -> Result<()> {
+        construction_loan::attest_milestone(ctx, milestone_index)
+    }
+
+    pub fn draw_construction_milestone(ctx: Context<DrawConstructionMilestone>) -> Result<()> {
+        construction_loan::draw_construction_milestone(ctx)
+    }
+
+    pub fn complete_construction_loan(ctx: Context<CompleteConstructionLoan>) -> Result<()> {
+        construction_loan::complete_construction_loan(ctx)
+    }
+
+    pub fn cancel_construction_loan(ctx: Context<CancelConstructionLoan>) -> Result<()> {
+        construction_loan::cancel_construction_loan(ctx)
+    }
+
+    pub fn liquidate_construction_loan(
+        ctx: Context<LiquidateConstructionLoan>,
+        proceeds: u64,
+    ) -> Result<()> {
+        construction_loan::liquidate_construction_loan(ctx, proceeds)
+    }
+
+    // Closing escrow instructions
+    pub fn open_closing_escrow(
+        ctx: Context<OpenClosingEscrow>,
+        purchase_price: u64,
+        buyer_loan_amount: u64,
+        buyer_lending_pool: Option<Pubkey>,
+    ) -> Result<()> {
+        closing_escrow::open_closing_escrow(ctx, purchase_price, buyer_loan_amount, buyer_lending_pool)
+    }
+
+    pub fn approve_closing_financing(ctx: Context<ApproveClosingFinancing>) -> Result<()> {
+        closing_escrow::approve_closing_financing(ctx)
+    }
+
+    pub fn deposit_purchase_price(ctx: Context<DepositPurchasePrice>) -> Result<()> {
+        closing_escrow::deposit_purchase_price(ctx)
+    }
+
+    pub fn settle_closing(ctx: Context<SettleClosing>) -> Result<()> {
+        closing_escrow::settle_closing(ctx)
+    }
+
+    pub fn cancel_closing_escrow(ctx: Context<CancelClosingEscrow>) -> Result<()> {
+        closing_escrow::cancel_closing_escrow(ctx)
+    }
+
+    // Mortgage assumption instructions
+    pub fn request_assumption(ctx: Context<RequestAssumption>) -> Result<()> {
+        assumption::request_assumption(ctx)
+    }
+
+    pub fn approve_assumption(ctx: Context<ApproveAssumption>, assumption_fee: u64) -> Result<()> {
+        assumption::approve_assumption(ctx, assumption_fee)
+    }
+
+    pub fn complete_assumption(ctx: Context<CompleteAssumption>) -> Result<()> {
+        assumption::complete_assumption(ctx)
+    }
+
+    pub fn cancel_assumption(ctx: Context<CancelAssumption>) -> Result<()> {
+        assumption::cancel_assumption(ctx)
+    }
+
+    // Refinancing instructions
+    pub fn refinance_mortgage(ctx: Context<RefinanceMortgage>) -> Result<()> {
+        refinance::refinance_mortgage(ctx)
+    }
+
+    // Syndication instructions
+    pub fn create_syndicate(
+        ctx: Context<CreateSyndicate>,
+        lending_pools: Vec<Pubkey>,
+        shares_bps: Vec<u16>,
+    ) -> Result<()> {
+        syndication::create_syndicate(ctx, lending_pools, shares_bps)
+    }
+
+    pub fn fund_participation(ctx: Context<FundParticipation>) -> Result<()> {
+        syndication::fund_participation(ctx)
+    }
+
+    pub fn make_syndicated_payment<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeSyndicated<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        syndication::make_syndicated_payment(ctx, amount)
+    }
+
+    pub fn distribute_syndicated_proceeds<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeSyndicatedProceeds<'info>>,
+        proceeds: u64,
+    ) -> Result<()> {
+        syndication::distribute_syndicated_proceeds(ctx, proceeds)
+    }
+
+    // Tranche instructions
+    pub fn initialize_tranches(ctx: Context<InitializeTranches>, senior_yield_cap: u64) -> Result<()> {
+        tranches::initialize_tranches(ctx, senior_yield_cap)
+    }
+
+    pub fn deposit_to_tranche(
+        ctx: Context<DepositToTranche>,
+        tranche: TrancheKind,
+        amount: u64,
+    ) -> Result<()> {
+        tranches::deposit_to_tranche(ctx, tranche, amount)
+    }
+
+    pub fn withdraw_from_tranche(ctx: Context<WithdrawFromTranche>, shares: u64) -> Result<()> {
+        tranches::withdraw_from_tranche(ctx, shares)
+    }
+
+    // Term deposit instructions
+    pub fn initialize_term_boost_reserve(ctx: Context<InitializeTermBoostReserve>) -> Result<()> {
+        term_deposits::initialize_term_boost_reserve(ctx)
+    }
+
+    pub fn fund_term_boost_reserve(ctx: Context<FundTermBoostReserve>, amount: u64) -> Result<()> {
+        term_deposits::fund_term_boost_reserve(ctx, amount)
+    }
+
+    pub fn open_term_deposit(
+        ctx: Context<OpenTermDeposit>,
+        deposit_id: u64,
+        term_years: u8,
+        amount: u64,
+    ) -> Result<()> {
+        term_deposits::open_term_deposit(ctx, deposit_id, term_years, amount)
+    }
+
+    pub fn redeem_term_deposit(ctx: Context<RedeemTermDeposit>) -> Result<()> {
+        term_deposits::redeem_term_deposit(ctx)
+    }
+
+    // Withdrawal queue instructions
+    pub fn initialize_withdrawal_queue(ctx: Context<InitializeWithdrawalQueue>) -> Result<()> {
+        withdrawal_queue::initialize_withdrawal_queue(ctx)
+    }
+
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
+        withdrawal_queue::request_withdrawal(ctx, shares)
+    }
+
+    pub fn process_withdrawal_queue(ctx: Context<ProcessWithdrawalQueue>) -> Result<()> {
+        withdrawal_queue::process_withdrawal_queue(ctx)
+    }
+
+    pub fn cancel_withdrawal_request(ctx: Context<CancelWithdrawalRequest>) -> Result<()> {
+        withdrawal_queue::cancel_withdrawal_request(ctx)
+    }
+
+    // Pool limit instructions
+    pub fn configure_pool_limits(
+        ctx: Context<ConfigurePoolLimits>,
+        max_total_deposits: u64,
+        max_deposit_per_lender: u64,
+        max_loan_share: u64,
+        max_borrower_exposure: u64,
+    ) -> Result<()> {
+        pool_limits::configure_pool_limits(
+            ctx,
+            max_total_deposits,
+            max_deposit_per_lender,
+            max_loan_share,
+            max_borrower_exposure,
+        )
+    }
+
+    pub fn initialize_borrower_exposure(ctx: Context<InitializeBorrowerExposure>) -> Result<()> {
+        pool_limits::initialize_borrower_exposure(ctx)
+    }
+
+    // Pool allowlist instructions
+    pub fn configure_pool_access(
+        ctx: Context<ConfigurePoolAccess>,
+        gatekeeper: Pubkey,
+        allowlist_enabled: bool,
+    ) -> Result<()> {
+        allowlist::configure_pool_access(ctx, gatekeeper, allowlist_enabled)
+    }
+
+    pub fn issue_pool_attestation(
+        ctx: Context<IssuePoolAttestation>,
+        can_deposit: bool,
+        can_borrow: bool,
+        expires_at: i64,
+    ) -> Result<()> {
+        allowlist::issue_pool_attestation(ctx, can_deposit, can_borrow, expires_at)
+    }
+
+    pub fn revoke_pool_attestation(ctx: Context<RevokePoolAttestation>) -> Result<()> {
+        allowlist::revoke_pool_attestation(ctx)
+    }
+
+    // Borrower identity instructions
+    pub fn register_kyc_provider(ctx: Context<RegisterKycProvider>) -> Result<()> {
+        identity::register_kyc_provider(ctx)
+    }
+
+    pub fn revoke_kyc_provider(ctx: Context<RevokeKycProvider>) -> Result<()> {
+        identity::revoke_kyc_provider(ctx)
+    }
+
+    pub fn issue_borrower_identity(
+        ctx: Context<IssueBorrowerIdentity>,
+        verification_level: u8,
+        jurisdiction: JurisdictionCode,
+        dossier_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        identity::issue_borrower_identity(
+            ctx,
+            verification_level,
+            jurisdiction,
+            dossier_hash,
+            expires_at,
+        )
+    }
+
+    pub fn revoke_borrower_identity(ctx: Context<RevokeBorrowerIdentity>) -> Result<()> {
+        identity::revoke_borrower_identity(ctx)
+    }
+
+    pub fn set_pool_verification_level(
+        ctx: Context<SetPoolVerificationLevel>,
+        min_verification_level: u8,
+    ) -> Result<()> {
+        identity::set_pool_verification_level(ctx, min_verification_level)
+    }
+
+    // Jurisdiction compliance instructions
+    pub fn set_jurisdiction_rule(
+        ctx: Context<SetJurisdictionRule>,
+        jurisdiction: JurisdictionCode,
+        max_interest_rate: u64,
+        max_ltv: u64,
+        disclosures_hash: [u8; 32],
+        allowed_products: u8,
+    ) -> Result<()> {
+        compliance::set_jurisdiction_rule(
+            ctx,
+            jurisdiction,
+            max_interest_rate,
+            max_ltv,
+            disclosures_hash,
+            allowed_products,
+        )
+    }
+
+    pub fn set_property_jurisdiction(
+        ctx: Context<SetPropertyJurisdiction>,
+        jurisdiction: JurisdictionCode,
+    ) -> Result<()> {
+        compliance::set_property_jurisdiction(ctx, jurisdiction)
+    }
+
+    // Property NFT minting instructions
+    pub fn mint_property_nft(
+        ctx: Context<MintPropertyNFT>,
+        property_value: u64,
+        property_address: String,
+        name: String,
+        symbol: String,
+        uri: String,
+        title_evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        property_mint::mint_property_nft(
+            ctx,
+            property_value,
+            property_address,
+            name,
+            symbol,
+            uri,
+            title_evidence_hash,
+        )
+    }
+
+    pub fn sync_property_freeze(ctx: Context<SyncPropertyFreeze>) -> Result<()> {
+        property_mint::sync_property_freeze(ctx)
+    }
+
+    // Property record instructions
+    pub fn create_property_record(
+        ctx: Context<CreatePropertyRecord>,
+        parcel_hash: [u8; 32],
+        parcel_id: String,
+        property_type: PropertyType,
+        square_feet: u32,
+        year_built: u16,
+        occupancy: Occupancy,
+    ) -> Result<()> {
+        property_records::create_property_record(
+            ctx,
+            parcel_hash,
+            parcel_id,
+            property_type,
+            square_feet,
+            year_built,
+            occupancy,
+        )
+    }
+
+    pub fn update_property_record(
+        ctx: Context<UpdatePropertyRecord>,
+        property_type: PropertyType,
+        square_feet: u32,
+        year_built: u16,
+        occupancy: Occupancy,
+    ) -> Result<()> {
+        property_records::update_property_record(
+            ctx,
+            property_type,
+            square_feet,
+            year_built,
+            occupancy,
+        )
+    }
+
+    pub fn attach_property_document(
+        ctx: Context<UpdatePropertyRecord>,
+        kind: DocumentKind,
+        hash: [u8; 32],
+    ) -> Result<()> {
+        property_records::attach_property_document(ctx, kind, hash)
+    }
+
+    // Title registry instructions
+    pub fn register_title_registrar(ctx: Context<RegisterTitleRegistrar>) -> Result<()> {
+        title_registry::register_title_registrar(ctx)
+    }
+
+    pub fn revoke_title_registrar(ctx: Context<RevokeTitleRegistrar>) -> Result<()> {
+        title_registry::revoke_title_registrar(ctx)
+    }
+
+    pub fn attest_title(ctx: Context<AttestTitle>, evidence_hash: [u8; 32]) -> Result<()> {
+        title_registry::attest_title(ctx, evidence_hash)
+    }
+
+    pub fn record_title_event(
+        ctx: Context<RecordTitleEvent>,
+        event: TitleEvent,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        title_registry::record_title_event(ctx, event, evidence_hash)
+    }
+
+    // Fractional property instructions
+    pub fn fractionalize_property(
+        ctx: Context<FractionalizeProperty>,
+        total_supply: u64,
+    ) -> Result<()> {
+        fractions::fractionalize_property(ctx, total_supply)
+    }
+
+    pub fn reassemble_property(ctx: Context<ReassembleProperty>) -> Result<()> {
+        fractions::reassemble_property(ctx)
+    }
+
+    pub fn pledge_fractions(
+        ctx: Context<PledgeFractions>,
+        loan_id: u64,
+        fractions: u64,
+        loan_amount: u64,
+    ) -> Result<()> {
+        fractions::pledge_fractions(ctx, loan_id, fractions, loan_amount)
+    }
+
+    pub fn repay_fraction_loan(ctx: Context<RepayFractionLoan>, amount: u64) -> Result<()> {
+        fractions::repay_fraction_loan(ctx, amount)
+    }
+
+    pub fn release_pledged_fractions(ctx: Context<ReleasePledgedFractions>) -> Result<()> {
+        fractions::release_pledged_fractions(ctx)
+    }
+
+    pub fn liquidate_fraction_loan(ctx: Context<LiquidateFractionLoan>) -> Result<()> {
+        fractions::liquidate_fraction_loan(ctx)
+    }
+
+    // Compressed property instructions
+    pub fn create_property_tree(
+        ctx: Context<CreatePropertyTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        compressed_properties::create_property_tree(ctx, max_depth, max_buffer_size)
+    }
+
+    pub fn register_compressed_property(
+        ctx: Context<RegisterCompressedProperty>,
+        property_value: u64,
+        property_address: String,
+        jurisdiction: JurisdictionCode,
+    ) -> Result<()> {
+        compressed_properties::register_compressed_property(
+            ctx,
+            property_value,
+            property_address,
+            jurisdiction,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn decompress_property<'info>(
+        ctx: Context<'_, '_, '_, 'info, DecompressProperty<'info>>,
+        root: [u8; 32],
+        property: CompressedProperty,
+        index: u32,
+        property_address: String,
+        name: String,
+        symbol: String,
+        uri: String,
+        title_evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        compressed_properties::decompress_property(
+            ctx,
+            root,
+            property,
+            index,
+            property_address,
+            name,
+            symbol,
+            uri,
+            title_evidence_hash,
+        )
+    }
+
+    // Migrations
+    pub fn migrate_lending_pool(ctx: Context<MigrateLendingPool>) -> Result<()> {
+        migrations::migrate_lending_pool(ctx)
+    }
+
+    pub fn migrate_property_nft(ctx: Context<MigratePropertyNFT>) -> Result<()> {
+        migrations::migrate_property_nft(ctx)
+    }
+}
@@ -0,0 +1,680 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::pool_limits::BorrowerExposure;
+use crate::property_mint::{freeze_property_nft, thaw_property_nft};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+use crate::tranches::apply_tranche_waterfall;
+
+/// Ordered liens recorded against a single property.
+/// `PropertyNFT.locked_by` always points at the first-position lien.
+#[account]
+#[derive(Default)]
+pub struct LienRegistry {
+    /// Property NFT account the liens encumber
+    pub property_nft: Pubkey,
+
+    /// Liens ordered by priority; index 0 is the first lien
+    pub liens: Vec<Lien>,
+
+    /// Surplus left for the owner after the last liquidation waterfall
+    pub owner_surplus: u64,
+
+    /// True once a liquidation waterfall has extinguished every lien, until the
+    /// next lien is recorded
+    pub settled: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Lien {
+    /// Mortgage holding this lien
+    pub mortgage: Pubkey,
+
+    /// Lending pool that funded the mortgage
+    pub lending_pool: Pubkey,
+
+    /// Lien position (1 = first lien)
+    pub position: u8,
+
+    /// Outstanding amount secured by the lien
+    pub amount: u64,
+
+    /// True if the mortgage behind this lien is in default
+    pub is_default: bool,
+
+    /// True if a more senior lien has defaulted
+    pub senior_default_notice: bool,
+
+    /// Proceeds allocated to this lien in the last liquidation waterfall
+    pub proceeds_allocated: u64,
+}
+
+impl Lien {
+    pub const LEN: usize = 32 + // mortgage
+                          32 + // lending_pool
+                          1 + // position
+                          8 + // amount
+                          1 + // is_default
+                          1 + // senior_default_notice
+                          8; // proceeds_allocated
+}
+
+impl LienRegistry {
+    pub const MAX_LIENS: usize = 4;
+
+    /// Maximum combined loan-to-value across all liens (basis points)
+    pub const MAX_COMBINED_LTV: u64 = 9_000;
+
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // property_nft
+                          4 + Self::MAX_LIENS * Lien::LEN + // liens
+                          8 + // owner_surplus
+                          1 + // settled
+                          1; // bump
+
+    pub fn combined_amount(&self) -> Result<u64> {
+        self.liens.iter().try_fold(0u64, |total, lien| {
+            total.checked_add(lien.amount).ok_or(ErrorCode::Overflow.into())
+        })
+    }
+
+    fn position(&self, mortgage: &Pubkey) -> Option<usize> {
+        self.liens.iter().position(|lien| lien.mortgage == *mortgage)
+    }
+
+    fn renumber(&mut self) {
+        for (index, lien) in self.liens.iter_mut().enumerate() {
+            lien.position = (index + 1) as u8;
+        }
+    }
+}
+
+#[event]
+pub struct SeniorLienDefaulted {
+    pub property_nft: Pubkey,
+    pub senior_mortgage: Pubkey,
+    pub senior_position: u8,
+    pub junior_mortgages: Vec<Pubkey>,
+}
+
+#[event]
+pub struct LienExtinguished {
+    pub property_nft: Pubkey,
+    pub mortgage: Pubkey,
+    pub position: u8,
+    pub proceeds: u64,
+    pub written_off: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeLienRegistry<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == owner.key() @ ErrorCode::InvalidNFTOwner,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    /// Existing mortgage that already locks the property, if any
+    pub senior_mortgage: Option<Account<'info, Mortgage>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = LienRegistry::LEN,
+        seeds = [b"lien_registry", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub lien_registry: Account<'info, LienRegistry>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RecordLien<'info> {
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == borrower.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        mut,
+        seeds = [b"lien_registry", property_nft.key().as_ref()],
+        bump = lien_registry.bump,
+    )]
+    pub lien_registry: Account<'info, LienRegistry>,
+
+    #[account(
+        constraint = mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = !mortgage.is_active @ ErrorCode::InvalidParameter,
+        constraint = mortgage.property_nft == property_nft.key() @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseLien<'info> {
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        seeds = [b"lien_registry", property_nft.key().as_ref()],
+        bump = lien_registry.bump,
+    )]
+    pub lien_registry: Account<'info, LienRegistry>,
+
+    #[account(
+        constraint = mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = mortgage.remaining_balance == 0 @ ErrorCode::LienOutstanding,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshLien<'info> {
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        seeds = [b"lien_registry", property_nft.key().as_ref()],
+        bump = lien_registry.bump,
+    )]
+    pub lien_registry: Account<'info, LienRegistry>,
+
+    pub mortgage: Account<'info, Mortgage>,
+}
+
+/// Remaining accounts: `(mortgage, lending_pool, stablecoin_vault, pool_tranches,
+/// borrower_exposure)` per lien, in priority order
+#[derive(Accounts)]
+pub struct DistributeLienProceeds<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        constraint = mortgage.lending_pool == lending_pool.key() @ ErrorCode::InvalidParameter,
+        constraint = mortgage.is_default @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        seeds = [b"lien_registry", property_nft.key().as_ref()],
+        bump = lien_registry.bump,
+    )]
+    pub lien_registry: Account<'info, LienRegistry>,
+
+    /// Account holding the sale proceeds of the foreclosed property
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Property owner's account receiving the surplus after all liens
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == property_nft.owner @ ErrorCode::InvalidNFTOwner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_lien_registry(ctx: Context<InitializeLienRegistry>) -> Result<()> {
+    let lien_registry = &mut ctx.accounts.lien_registry;
+    let property_nft = &ctx.accounts.property_nft;
+
+    lien_registry.property_nft = property_nft.key();
+    lien_registry.liens = Vec::new();
+    lien_registry.owner_surplus = 0;
+    lien_registry.settled = false;
+    lien_registry.bump = ctx.bumps.lien_registry;
+
+    // A property that is already locked carries its mortgage as the first lien
+    if let Some(locked_by) = property_nft.locked_by {
+        let senior_mortgage = ctx.accounts.senior_mortgage
+            .as_ref()
+            .ok_or(ErrorCode::InvalidParameter)?;
+        require_keys_eq!(senior_mortgage.key(), locked_by, ErrorCode::InvalidParameter);
+
+        lien_registry.liens.push(Lien {
+            mortgage: locked_by,
+            lending_pool: senior_mortgage.lending_pool,
+            position: 1,
+            amount: senior_mortgage.remaining_balance,
+            is_default: senior_mortgage.is_default,
+            senior_default_notice: false,
+            proceeds_allocated: 0,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn record_lien(ctx: Context<RecordLien>) -> Result<()> {
+    let lien_registry = &mut ctx.accounts.lien_registry;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let mortgage = &ctx.accounts.mortgage;
+
+    require!(
+        lien_registry.liens.len() < LienRegistry::MAX_LIENS,
+        ErrorCode::TooManyLiens
+    );
+    require!(
+        lien_registry.position(&mortgage.key()).is_none(),
+        ErrorCode::NFTAlreadyLocked
+    );
+    require!(property_nft.property_value > 0, ErrorCode::InvalidParameter);
+    // The first lien takes the property lock, so it can't already be pledged elsewhere
+    require!(
+        !lien_registry.liens.is_empty() || !property_nft.is_locked,
+        ErrorCode::NFTAlreadyLocked
+    );
+
+    // The first lien after a foreclosure starts a fresh registry
+    if lien_registry.settled {
+        lien_registry.settled = false;
+        lien_registry.owner_surplus = 0;
+    }
+
+    let combined = lien_registry
+        .combined_amount()?
+        .checked_add(mortgage.loan_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    let combined_ltv = (combined as u128)
+        .checked_mul(10_000)
+        .ok_or(ErrorCode::Overflow)?
+        / property_nft.property_value as u128;
+    require!(
+        combined_ltv <= LienRegistry::MAX_COMBINED_LTV as u128,
+        ErrorCode::CombinedLtvExceeded
+    );
+
+    let position = (lien_registry.liens.len() + 1) as u8;
+    lien_registry.liens.push(Lien {
+        mortgage: mortgage.key(),
+        lending_pool: mortgage.lending_pool,
+        position,
+        amount: mortgage.loan_amount,
+        is_default: false,
+        senior_default_notice: false,
+        proceeds_allocated: 0,
+    });
+
+    if position == 1 {
+        property_nft.is_locked = true;
+        property_nft.locked_by = Some(mortgage.key());
+    }
+
+    freeze_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &property_nft.owner,
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn release_lien(ctx: Context<ReleaseLien>) -> Result<()> {
+    let lien_registry = &mut ctx.accounts.lien_registry;
+    let property_nft = &mut ctx.accounts.property_nft;
+
+    let index = lien_registry
+        .position(&ctx.accounts.mortgage.key())
+        .ok_or(ErrorCode::LienNotFound)?;
+    lien_registry.liens.remove(index);
+    lien_registry.renumber();
+
+    // Junior liens move up; the property stays locked to whoever is now first
+    match lien_registry.liens.first() {
+        Some(first) => {
+            property_nft.is_locked = true;
+            property_nft.locked_by = Some(first.mortgage);
+        }
+        None => {
+            property_nft.is_locked = false;
+            property_nft.locked_by = None;
+            thaw_property_nft(
+                &ctx.accounts.property_mint,
+                &ctx.accounts.property_token_account,
+                &property_nft.owner,
+                &ctx.accounts.property_mint_authority,
+                ctx.bumps.property_mint_authority,
+                &ctx.accounts.token_program,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn refresh_lien(ctx: Context<RefreshLien>) -> Result<()> {
+    let lien_registry = &mut ctx.accounts.lien_registry;
+    let mortgage = &ctx.accounts.mortgage;
+
+    let index = lien_registry
+        .position(&mortgage.key())
+        .ok_or(ErrorCode::LienNotFound)?;
+
+    let newly_defaulted = mortgage.is_default && !lien_registry.liens[index].is_default;
+    lien_registry.liens[index].amount = mortgage.remaining_balance;
+    lien_registry.liens[index].is_default = mortgage.is_default;
+
+    if newly_defaulted && index + 1 < lien_registry.liens.len() {
+        let mut junior_mortgages = Vec::new();
+        for junior in lien_registry.liens[index + 1..].iter_mut() {
+            junior.senior_default_notice = true;
+            junior_mortgages.push(junior.mortgage);
+        }
+
+        emit!(SeniorLienDefaulted {
+            property_nft: lien_registry.property_nft,
+            senior_mortgage: mortgage.key(),
+            senior_position: lien_registry.liens[index].position,
+            junior_mortgages,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn distribute_lien_proceeds<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeLienProceeds<'info>>,
+    proceeds: u64,
+) -> Result<()> {
+    let lien_registry = &mut ctx.accounts.lien_registry;
+    let defaulted = ctx.accounts.mortgage.key();
+    let clock = Clock::get()?;
+
+    require!(proceeds > 0, ErrorCode::InvalidParameter);
+    require!(
+        lien_registry.position(&defaulted).is_some(),
+        ErrorCode::LienNotFound
+    );
+
+    // Pay liens strictly in priority order against their current balances; the
+    // foreclosure extinguishes every lien, so each pool writes off whatever its
+    // share of the proceeds didn't cover, and anything left belongs to the owner
+    let mut remaining = proceeds;
+    for (index, lien) in lien_registry.liens.iter().enumerate() {
+        let (mut mortgage, mut lending_pool, vault, pool_tranches, mut borrower_exposure) =
+            lien_accounts(ctx.remaining_accounts, index, lien)?;
+        let payout = remaining.min(mortgage.remaining_balance);
+
+        if payout > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.authority_token_account.to_account_info(),
+                        to: vault,
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                payout,
+            )?;
+        }
+
+        let outstanding = mortgage.remaining_balance;
+        let shortfall = outstanding - payout;
+        lending_pool.total_borrowed = lending_pool.total_borrowed.saturating_sub(outstanding);
+        lending_pool.release_term_liquidity(outstanding, mortgage.loan_duration);
+        lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(shortfall);
+        lending_pool.last_updated = clock.unix_timestamp;
+        borrower_exposure.sub(outstanding);
+
+        mortgage.remaining_balance = 0;
+        mortgage.is_active = false;
+        mortgage.close_date = Some(clock.unix_timestamp);
+
+        mortgage.exit(&crate::ID)?;
+        lending_pool.exit(&crate::ID)?;
+        borrower_exposure.exit(&crate::ID)?;
+        apply_tranche_waterfall(&lending_pool.key(), &pool_tranches, 0, shortfall)?;
+
+        emit!(LienExtinguished {
+            property_nft: lien_registry.property_nft,
+            mortgage: lien.mortgage,
+            position: lien.position,
+            proceeds: payout,
+            written_off: shortfall,
+        });
+        remaining -= payout;
+    }
+
+    if remaining > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            remaining,
+        )?;
+    }
+    lien_registry.liens.clear();
+    lien_registry.owner_surplus = remaining;
+    lien_registry.settled = true;
+
+    // With every lien extinguished the property is free; the foreclosure sale
+    // is recorded as a title transfer by the registrar
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+    thaw_property_nft(
+        &ctx.accounts.property_mint,
+        &ctx.accounts.property_token_account,
+        &property_nft.owner,
+        &ctx.accounts.property_mint_authority,
+        ctx.bumps.property_mint_authority,
+        &ctx.accounts.token_program,
+    )
+}
+
+/// Hands the lien held by `mortgage` over to `replacement` when the loan is
+/// refinanced, assumed or replaced by a buyer's loan, or extinguishes it when the
+/// loan is paid off with nothing in its place. Properties without a registry have
+/// nothing to update. With `sole_lien`, any other recorded lien blocks the change:
+/// junior liens are owed by the current owner and can't follow the property.
+pub fn hand_over_lien(
+    lien_registry: &AccountInfo,
+    property_nft: &Pubkey,
+    mortgage: &Pubkey,
+    replacement: Option<&Account<Mortgage>>,
+    sole_lien: bool,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"lien_registry", property_nft.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(lien_registry.key(), expected, ErrorCode::InvalidLienAccounts);
+    if lien_registry.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut registry = Account::<LienRegistry>::try_from(lien_registry)?;
+    if sole_lien {
+        require!(
+            registry.liens.iter().all(|lien| lien.mortgage == *mortgage),
+            ErrorCode::LienOutstanding
+        );
+    }
+    let index = match registry.position(mortgage) {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+
+    match replacement {
+        Some(new_mortgage) => {
+            let lien = &mut registry.liens[index];
+            lien.mortgage = new_mortgage.key();
+            lien.lending_pool = new_mortgage.lending_pool;
+            lien.amount = new_mortgage.remaining_balance;
+            lien.is_default = new_mortgage.is_default;
+        }
+        None => {
+            registry.liens.remove(index);
+            registry.renumber();
+        }
+    }
+    registry.exit(&crate::ID)
+}
+
+/// Loads the `(mortgage, lending_pool, stablecoin_vault, pool_tranches, borrower_exposure)`
+/// accounts passed for the lien at `index`
+fn lien_accounts<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    index: usize,
+    lien: &Lien,
+) -> Result<(
+    Account<'info, Mortgage>,
+    Account<'info, LendingPool>,
+    AccountInfo<'info>,
+    AccountInfo<'info>,
+    Account<'info, BorrowerExposure>,
+)> {
+    let accounts = remaining_accounts
+        .get(index * 5..index * 5 + 5)
+        .ok_or(ErrorCode::InvalidLienAccounts)?;
+    let (mortgage_info, pool_info, vault_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+    let mortgage = Account::<Mortgage>::try_from(mortgage_info)?;
+    require_keys_eq!(mortgage.key(), lien.mortgage, ErrorCode::InvalidLienAccounts);
+    let lending_pool = Account::<LendingPool>::try_from(pool_info)?;
+    require_keys_eq!(
+        lending_pool.key(),
+        lien.lending_pool,
+        ErrorCode::InvalidLienAccounts
+    );
+    require_keys_eq!(
+        vault_info.key(),
+        lending_pool.stablecoin_vault,
+        ErrorCode::InvalidLienAccounts
+    );
+
+    let borrower_exposure = Account::<BorrowerExposure>::try_from(&accounts[4])?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"borrower_exposure", mortgage.borrower.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        borrower_exposure.key(),
+        expected,
+        ErrorCode::InvalidLienAccounts
+    );
+
+    Ok((
+        mortgage,
+        lending_pool,
+        vault_info.clone(),
+        accounts[3].clone(),
+        borrower_exposure,
+    ))
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+/// Byte offset of `LendingPool.bump`, the first field appended after the original layout
+const LENDING_POOL_BUMP_OFFSET: usize = 8 + // discriminator
+                                        32 + // authority
+                                        32 + // stablecoin_vault
+                                        8 + // interest_rate
+                                        8 + // loan_duration
+                                        8 + // total_deposited
+                                        8 + // total_borrowed
+                                        1 + // is_active
+                                        8; // last_updated
+
+#[derive(Accounts)]
+pub struct MigrateLendingPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: pool account in either the original or current layout; the
+    /// discriminator, owner, authority and PDA address are verified in the handler
+    #[account(mut, owner = crate::ID @ ErrorCode::InvalidParameter)]
+    pub lending_pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a pool account to the current `LendingPool` layout and records its PDA bump.
+/// Appended fields start zeroed; safe to call again on an already migrated pool.
+pub fn migrate_lending_pool(ctx: Context<MigrateLendingPool>) -> Result<()> {
+    let pool_info = ctx.accounts.lending_pool.to_account_info();
+    let authority = ctx.accounts.authority.key();
+
+    {
+        let data = pool_info.try_borrow_data()?;
+        require!(
+            data.len() >= LENDING_POOL_BUMP_OFFSET && data[..8] == LendingPool::DISCRIMINATOR,
+            ErrorCode::InvalidParameter
+        );
+        require!(data[8..40] == authority.to_bytes(), ErrorCode::Unauthorized);
+    }
+
+    let (expected, bump) =
+        Pubkey::find_program_address(&[b"lending_pool", authority.as_ref()], &crate::ID);
+    require_keys_eq!(pool_info.key(), expected, ErrorCode::InvalidParameter);
+
+    if pool_info.data_len() < LendingPool::LEN {
+        let required = Rent::get()?.minimum_balance(LendingPool::LEN);
+        let shortfall = required.saturating_sub(pool_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: pool_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        pool_info.realloc(LendingPool::LEN, true)?;
+    }
+
+    pool_info.try_borrow_mut_data()?[LENDING_POOL_BUMP_OFFSET] = bump;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePropertyNFT<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: property account in any earlier layout; the discriminator, owner
+    /// and PDA address are verified in the handler
+    #[account(mut, owner = crate::ID @ ErrorCode::InvalidParameter)]
+    pub property_nft: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a property account to the current `PropertyNFT` size. Fields appended
+/// since the original layout read as zeroed, so the property is untagged, has no
+/// record and needs a fresh assessment before it can back a loan. Only adds
+/// zeroed space, so anyone may pay for it; safe to call again.
+pub fn migrate_property_nft(ctx: Context<MigratePropertyNFT>) -> Result<()> {
+    let property_info = ctx.accounts.property_nft.to_account_info();
+
+    let mint = {
+        let data = property_info.try_borrow_data()?;
+        require!(
+            data.len() >= 72 && data[..8] == PropertyNFT::DISCRIMINATOR,
+            ErrorCode::InvalidParameter
+        );
+        // owner occupies bytes 8..40, mint 40..72
+        Pubkey::try_from(&data[40..72]).map_err(|_| ErrorCode::InvalidParameter)?
+    };
+
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"property_nft", mint.as_ref()], &crate::ID);
+    require_keys_eq!(property_info.key(), expected, ErrorCode::InvalidParameter);
+
+    if property_info.data_len() < PropertyNFT::MAX_LEN {
+        let required = Rent::get()?.minimum_balance(PropertyNFT::MAX_LEN);
+        let shortfall = required.saturating_sub(property_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: property_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        property_info.realloc(PropertyNFT::MAX_LEN, true)?;
+    }
+
+    Ok(())
+}
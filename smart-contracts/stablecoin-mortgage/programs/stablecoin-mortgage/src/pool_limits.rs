@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+/// Deposit caps and concentration limits for a lending pool. A limit of 0 means uncapped.
+#[account]
+#[derive(Default)]
+pub struct PoolLimits {
+    /// Lending pool the limits apply to
+    pub lending_pool: Pubkey,
+
+    /// Maximum total deposits in the pool
+    pub max_total_deposits: u64,
+
+    /// Maximum amount a single lender may have deposited
+    pub max_deposit_per_lender: u64,
+
+    /// Maximum single loan as a share of pool deposits (basis points)
+    pub max_loan_share: u64,
+
+    /// Maximum outstanding balance per borrower across their mortgages
+    pub max_borrower_exposure: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PoolLimits {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // lending_pool
+                          8 + // max_total_deposits
+                          8 + // max_deposit_per_lender
+                          8 + // max_loan_share
+                          8 + // max_borrower_exposure
+                          1; // bump
+
+    /// Checks a deposit of `amount` against the pool and per-lender caps
+    pub fn check_deposit(
+        &self,
+        lending_pool: &LendingPool,
+        lender_deposited: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let pool_total = lending_pool.total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            self.max_total_deposits == 0 || pool_total <= self.max_total_deposits,
+            ErrorCode::ExceedsPoolLimit
+        );
+
+        let lender_total = lender_deposited.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        require!(
+            self.max_deposit_per_lender == 0 || lender_total <= self.max_deposit_per_lender,
+            ErrorCode::ExceedsLenderLimit
+        );
+        Ok(())
+    }
+
+    /// Checks a single loan of `amount` against the pool concentration limit
+    pub fn check_loan(&self, lending_pool: &LendingPool, amount: u64) -> Result<()> {
+        if self.max_loan_share == 0 {
+            return Ok(());
+        }
+        let max_loan = (lending_pool.total_deposited as u128)
+            .checked_mul(self.max_loan_share as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / 10_000;
+        require!(amount as u128 <= max_loan, ErrorCode::ExceedsLoanConcentration);
+        Ok(())
+    }
+
+    /// Checks that adding `amount` keeps the borrower within their exposure cap
+    pub fn check_borrower_exposure(&self, exposure: &BorrowerExposure, amount: u64) -> Result<()> {
+        let total = exposure.outstanding.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        require!(
+            self.max_borrower_exposure == 0 || total <= self.max_borrower_exposure,
+            ErrorCode::ExceedsBorrowerExposure
+        );
+        Ok(())
+    }
+}
+
+/// Loads the pool's limits, or `None` when the `["pool_limits", pool]` PDA was never
+/// initialized and the pool is uncapped
+fn load_pool_limits<'info>(
+    lending_pool: &Pubkey,
+    pool_limits: &AccountInfo<'info>,
+) -> Result<Option<Account<'info, PoolLimits>>> {
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"pool_limits", lending_pool.as_ref()], &crate::ID);
+    require_keys_eq!(pool_limits.key(), expected, ErrorCode::InvalidParameter);
+    if pool_limits.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Account::<PoolLimits>::try_from(pool_limits)?))
+}
+
+/// Enforces the pool's deposit caps for a deposit of `amount` by a lender
+/// who already has `lender_deposited` in the pool
+pub fn enforce_deposit_limits(
+    lending_pool: &Account<LendingPool>,
+    pool_limits: &AccountInfo,
+    lender_deposited: u64,
+    amount: u64,
+) -> Result<()> {
+    match load_pool_limits(&lending_pool.key(), pool_limits)? {
+        Some(limits) => limits.check_deposit(lending_pool, lender_deposited, amount),
+        None => Ok(()),
+    }
+}
+
+/// Enforces the pool's concentration limit on a loan of `loan_amount` and the
+/// borrower exposure cap on `new_exposure` of additional funded balance
+pub fn enforce_loan_limits(
+    lending_pool: &Account<LendingPool>,
+    pool_limits: &AccountInfo,
+    borrower_exposure: &BorrowerExposure,
+    loan_amount: u64,
+    new_exposure: u64,
+) -> Result<()> {
+    match load_pool_limits(&lending_pool.key(), pool_limits)? {
+        Some(limits) => {
+            limits.check_loan(lending_pool, loan_amount)?;
+            limits.check_borrower_exposure(borrower_exposure, new_exposure)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Outstanding balance of one borrower across all their loans, in every pool.
+/// Each pool caps it with its own `max_borrower_exposure`.
+#[account]
+#[derive(Default)]
+pub struct BorrowerExposure {
+    /// Borrower wallet
+    pub borrower: Pubkey,
+
+    /// Sum of funded loan balances not yet repaid
+    pub outstanding: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl BorrowerExposure {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // borrower
+                          8 + // outstanding
+                          1; // bump
+
+    pub fn add(&mut self, amount: u64) -> Result<()> {
+        self.outstanding = self.outstanding.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn sub(&mut self, amount: u64) {
+        self.outstanding = self.outstanding.saturating_sub(amount);
+    }
+}
+
+#[derive(Accounts)]
+pub struct ConfigurePoolLimits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PoolLimits::LEN,
+        seeds = [b"pool_limits", lending_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_limits: Account<'info, PoolLimits>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBorrowerExposure<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = BorrowerExposure::LEN,
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn configure_pool_limits(
+    ctx: Context<ConfigurePoolLimits>,
+    max_total_deposits: u64,
+    max_deposit_per_lender: u64,
+    max_loan_share: u64,
+    max_borrower_exposure: u64,
+) -> Result<()> {
+    require!(max_loan_share <= 10_000, ErrorCode::InvalidParameter);
+
+    let pool_limits = &mut ctx.accounts.pool_limits;
+    pool_limits.lending_pool = ctx.accounts.lending_pool.key();
+    pool_limits.max_total_deposits = max_total_deposits;
+    pool_limits.max_deposit_per_lender = max_deposit_per_lender;
+    pool_limits.max_loan_share = max_loan_share;
+    pool_limits.max_borrower_exposure = max_borrower_exposure;
+    pool_limits.bump = ctx.bumps.pool_limits;
+
+    Ok(())
+}
+
+pub fn initialize_borrower_exposure(ctx: Context<InitializeBorrowerExposure>) -> Result<()> {
+    let borrower_exposure = &mut ctx.accounts.borrower_exposure;
+    borrower_exposure.borrower = ctx.accounts.borrower.key();
+    borrower_exposure.outstanding = 0;
+    borrower_exposure.bump = ctx.bumps.borrower_exposure;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(total_deposited: u64) -> LendingPool {
+        LendingPool {
+            total_deposited,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn zero_limits_are_uncapped() {
+        let limits = PoolLimits::default();
+        let exposure = BorrowerExposure {
+            outstanding: u64::MAX / 2,
+            ..Default::default()
+        };
+        assert!(limits.check_deposit(&pool(u64::MAX / 2), u64::MAX / 2, 1).is_ok());
+        assert!(limits.check_loan(&pool(0), u64::MAX).is_ok());
+        assert!(limits.check_borrower_exposure(&exposure, 1).is_ok());
+    }
+
+    #[test]
+    fn check_deposit_enforces_pool_and_lender_caps() {
+        let limits = PoolLimits {
+            max_total_deposits: 1_000,
+            max_deposit_per_lender: 300,
+            ..Default::default()
+        };
+        assert!(limits.check_deposit(&pool(700), 0, 300).is_ok());
+        assert_eq!(
+            limits.check_deposit(&pool(701), 0, 300).unwrap_err(),
+            ErrorCode::ExceedsPoolLimit.into()
+        );
+        assert_eq!(
+            limits.check_deposit(&pool(0), 200, 101).unwrap_err(),
+            ErrorCode::ExceedsLenderLimit.into()
+        );
+    }
+
+    #[test]
+    fn check_loan_caps_share_of_deposits() {
+        let limits = PoolLimits {
+            max_loan_share: 2_000,
+            ..Default::default()
+        };
+        assert!(limits.check_loan(&pool(1_000_000), 200_000).is_ok());
+        assert_eq!(
+            limits.check_loan(&pool(1_000_000), 200_001).unwrap_err(),
+            ErrorCode::ExceedsLoanConcentration.into()
+        );
+    }
+
+    #[test]
+    fn check_borrower_exposure_includes_outstanding() {
+        let limits = PoolLimits {
+            max_borrower_exposure: 500,
+            ..Default::default()
+        };
+        let exposure = BorrowerExposure {
+            outstanding: 400,
+            ..Default::default()
+        };
+        assert!(limits.check_borrower_exposure(&exposure, 100).is_ok());
+        assert_eq!(
+            limits.check_borrower_exposure(&exposure, 101).unwrap_err(),
+            ErrorCode::ExceedsBorrowerExposure.into()
+        );
+    }
+}
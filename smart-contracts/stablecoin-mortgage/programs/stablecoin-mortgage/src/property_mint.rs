@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::mpl_token_metadata::types::{Creator, DataV2};
+use anchor_spl::metadata::{self, CreateMetadataAccountsV3, Metadata};
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+
+/// Token Metadata field limits
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+
+/// Maximum property address length stored in `PropertyNFT`
+pub const MAX_PROPERTY_ADDRESS_LEN: usize = 100;
+
+/// Checks that `token_account` holds the property NFT for `holder` and that the
+/// program PDA can freeze it
+fn check_property_token_account(
+    mint: &Account<Mint>,
+    token_account: &Account<TokenAccount>,
+    holder: &Pubkey,
+    mint_authority: &AccountInfo,
+) -> Result<()> {
+    require!(
+        mint.freeze_authority == COption::Some(mint_authority.key()),
+        ErrorCode::PropertyNotFreezable
+    );
+    require!(
+        token_account.mint == mint.key() && token_account.amount == 1,
+        ErrorCode::InvalidParameter
+    );
+    require!(token_account.owner == *holder, ErrorCode::InvalidNFTOwner);
+    Ok(())
+}
+
+/// Freezes a locked property NFT in the holder's wallet so it can't be moved
+pub fn freeze_property_nft<'info>(
+    mint: &Account<'info, Mint>,
+    token_account: &Account<'info, TokenAccount>,
+    holder: &Pubkey,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    check_property_token_account(mint, token_account, holder, mint_authority)?;
+    if token_account.is_frozen() {
+        return Ok(());
+    }
+
+    let seeds = &[b"property_mint_authority".as_ref(), &[mint_authority_bump]];
+    let signer = &[&seeds[..]];
+
+    token::freeze_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::FreezeAccount {
+            account: token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: mint_authority.clone(),
+        },
+        signer,
+    ))
+}
+
+/// Thaws a property NFT frozen by `freeze_property_nft`
+pub fn thaw_property_nft<'info>(
+    mint: &Account<'info, Mint>,
+    token_account: &Account<'info, TokenAccount>,
+    holder: &Pubkey,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    check_property_token_account(mint, token_account, holder, mint_authority)?;
+    if !token_account.is_frozen() {
+        return Ok(());
+    }
+
+    let seeds = &[b"property_mint_authority".as_ref(), &[mint_authority_bump]];
+    let signer = &[&seeds[..]];
+
+    token::thaw_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::ThawAccount {
+            account: token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: mint_authority.clone(),
+        },
+        signer,
+    ))
+}
+
+#[derive(Accounts)]
+pub struct MintPropertyNFT<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Title registrar co-signing the registration
+    pub registrar: Signer<'info>,
+
+    #[account(
+        seeds = [b"title_registrar", registrar.key().as_ref()],
+        bump = title_registrar.bump,
+        constraint = title_registrar.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = PropertyNFT::MAX_LEN,
+        seeds = [b"property_nft", mint.key().as_ref()],
+        bump,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = TitleAttestation::LEN,
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA acting as mint, freeze and metadata update authority for property NFTs
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: metadata PDA, created and validated by the Token Metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn mint_property_nft(
+    ctx: Context<MintPropertyNFT>,
+    property_value: u64,
+    property_address: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    title_evidence_hash: [u8; 32],
+) -> Result<()> {
+    issue_property_nft(
+        ctx.accounts,
+        &ctx.bumps,
+        property_value,
+        property_address,
+        JurisdictionCode::default(),
+        name,
+        symbol,
+        uri,
+        title_evidence_hash,
+    )
+}
+
+/// Mints the property NFT, creates its `PropertyNFT` record and attests title.
+/// Shared with decompression of compressed property records.
+#[allow(clippy::too_many_arguments)]
+pub fn issue_property_nft<'info>(
+    accounts: &mut MintPropertyNFT<'info>,
+    bumps: &MintPropertyNFTBumps,
+    property_value: u64,
+    property_address: String,
+    jurisdiction: JurisdictionCode,
+    name: String,
+    symbol: String,
+    uri: String,
+    title_evidence_hash: [u8; 32],
+) -> Result<()> {
+    require!(property_value > 0, ErrorCode::InvalidParameter);
+    require!(
+        property_address.len() <= MAX_PROPERTY_ADDRESS_LEN,
+        ErrorCode::InvalidParameter
+    );
+    require!(
+        name.len() <= MAX_NAME_LEN && symbol.len() <= MAX_SYMBOL_LEN && uri.len() <= MAX_URI_LEN,
+        ErrorCode::InvalidParameter
+    );
+
+    let seeds = &[
+        b"property_mint_authority".as_ref(),
+        &[bumps.mint_authority],
+    ];
+    let signer = &[&seeds[..]];
+
+    // The mint authority PDA verifies itself as sole creator, so wallets can
+    // tell program-issued property NFTs from look-alikes
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: accounts.metadata.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                mint_authority: accounts.mint_authority.to_account_info(),
+                payer: accounts.owner.to_account_info(),
+                update_authority: accounts.mint_authority.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                rent: accounts.rent.to_account_info(),
+            },
+            signer,
+        ),
+        DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: Some(vec![Creator {
+                address: accounts.mint_authority.key(),
+                verified: true,
+                share: 100,
+            }]),
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: accounts.mint.to_account_info(),
+                to: accounts.token_account.to_account_info(),
+                authority: accounts.mint_authority.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    // Revoking the mint authority fixes the supply at one
+    token::set_authority(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            token::SetAuthority {
+                current_authority: accounts.mint_authority.to_account_info(),
+                account_or_mint: accounts.mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let property_nft = &mut accounts.property_nft;
+    property_nft.owner = accounts.owner.key();
+    property_nft.mint = accounts.mint.key();
+    property_nft.token_account = accounts.token_account.key();
+    property_nft.property_value = property_value;
+    property_nft.property_address = property_address;
+    property_nft.is_locked = false;
+    property_nft.locked_by = None;
+    property_nft.registration_date = Clock::get()?.unix_timestamp;
+    // The declared value stays unusable for lending until an assessment confirms it
+    property_nft.value_updated_at = property_nft.registration_date;
+    property_nft.value_current = false;
+    property_nft.jurisdiction = jurisdiction;
+    property_nft.record = None;
+
+    accounts.title_attestation.attest(
+        property_nft.key(),
+        accounts.registrar.key(),
+        accounts.owner.key(),
+        title_evidence_hash,
+        bumps.title_attestation,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncPropertyFreeze<'info> {
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(constraint = property_mint.key() == property_nft.mint @ ErrorCode::InvalidParameter)]
+    pub property_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = property_token_account.key() == property_nft.token_account @ ErrorCode::InvalidParameter,
+    )]
+    pub property_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: property mint freeze authority PDA
+    #[account(seeds = [b"property_mint_authority"], bump)]
+    pub property_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Brings the NFT's frozen state in line with `is_locked`, for lock changes
+/// made by instructions that don't freeze or thaw themselves
+pub fn sync_property_freeze(ctx: Context<SyncPropertyFreeze>) -> Result<()> {
+    let accounts = &ctx.accounts;
+    if accounts.property_nft.is_locked {
+        freeze_property_nft(
+            &accounts.property_mint,
+            &accounts.property_token_account,
+            &accounts.property_nft.owner,
+            &accounts.property_mint_authority,
+            ctx.bumps.property_mint_authority,
+            &accounts.token_program,
+        )
+    } else {
+        thaw_property_nft(
+            &accounts.property_mint,
+            &accounts.property_token_account,
+            &accounts.property_nft.owner,
+            &accounts.property_mint_authority,
+            ctx.bumps.property_mint_authority,
+            &accounts.token_program,
+        )
+    }
+}
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PropertyType {
+    #[default]
+    SingleFamily,
+    MultiFamily,
+    Condominium,
+    Townhouse,
+    Commercial,
+    Land,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Occupancy {
+    #[default]
+    OwnerOccupied,
+    SecondHome,
+    Investment,
+    Vacant,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    Deed,
+    TitleReport,
+    Appraisal,
+    Survey,
+    Insurance,
+    Other,
+}
+
+/// Hash of an off-chain document attached to a property record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PropertyDocument {
+    /// Kind of document
+    pub kind: DocumentKind,
+
+    /// Hash of the document contents
+    pub hash: [u8; 32],
+
+    /// Date the hash was attached
+    pub attached_at: i64,
+}
+
+impl PropertyDocument {
+    pub const LEN: usize = 1 + // kind
+                          32 + // hash
+                          8; // attached_at
+}
+
+/// Structured record of a property, one per parcel.
+/// The PDA is derived from the jurisdiction and normalized parcel id, so a
+/// parcel can only be registered once.
+#[account]
+#[derive(Default)]
+pub struct PropertyRecord {
+    /// Property NFT the record describes
+    pub property_nft: Pubkey,
+
+    /// Normalized parcel / APN identifier
+    pub parcel_id: String,
+
+    /// Jurisdiction the parcel is recorded in
+    pub jurisdiction: JurisdictionCode,
+
+    /// Property type
+    pub property_type: PropertyType,
+
+    /// Living area in square feet
+    pub square_feet: u32,
+
+    /// Year the structure was built (0 for land)
+    pub year_built: u16,
+
+    /// Occupancy of the property
+    pub occupancy: Occupancy,
+
+    /// Attached document hashes, at most one per kind
+    pub documents: Vec<PropertyDocument>,
+
+    /// Last update date
+    pub updated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PropertyRecord {
+    pub const MAX_PARCEL_ID_LEN: usize = 32;
+    pub const MAX_DOCUMENTS: usize = 6;
+
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // property_nft
+                          4 + Self::MAX_PARCEL_ID_LEN + // parcel_id
+                          8 + // jurisdiction
+                          1 + // property_type
+                          4 + // square_feet
+                          2 + // year_built
+                          1 + // occupancy
+                          4 + Self::MAX_DOCUMENTS * PropertyDocument::LEN + // documents
+                          8 + // updated_at
+                          1; // bump
+
+    /// Uppercases the parcel id and strips the separators counties format it with
+    pub fn normalize_parcel_id(parcel_id: &str) -> Result<String> {
+        require!(
+            parcel_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ' ' | '/')),
+            ErrorCode::InvalidParcelId
+        );
+        let normalized: String = parcel_id
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        require!(
+            !normalized.is_empty() && normalized.len() <= Self::MAX_PARCEL_ID_LEN,
+            ErrorCode::InvalidParcelId
+        );
+        Ok(normalized)
+    }
+
+    /// PDA seed for a normalized parcel id
+    pub fn parcel_hash(jurisdiction: &JurisdictionCode, normalized_parcel_id: &str) -> [u8; 32] {
+        hashv(&[jurisdiction.as_ref(), normalized_parcel_id.as_bytes()]).to_bytes()
+    }
+
+    /// PDA seed of this record
+    pub fn seed_hash(&self) -> [u8; 32] {
+        Self::parcel_hash(&self.jurisdiction, &self.parcel_id)
+    }
+
+    /// True if the record describes `property_nft` in its current jurisdiction
+    pub fn describes(&self, property_nft: &Account<PropertyNFT>) -> bool {
+        self.property_nft == property_nft.key() && self.jurisdiction == property_nft.jurisdiction
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(parcel_hash: [u8; 32])]
+pub struct CreatePropertyRecord<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == owner.key() @ ErrorCode::InvalidNFTOwner,
+        constraint = property_nft.record.is_none() @ ErrorCode::PropertyAlreadyRecorded,
+        constraint = property_nft.jurisdiction != JurisdictionCode::default() @ ErrorCode::InvalidParameter,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = PropertyRecord::LEN,
+        seeds = [b"property_record", parcel_hash.as_ref()],
+        bump,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePropertyRecord<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == owner.key() @ ErrorCode::InvalidNFTOwner,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        mut,
+        constraint = property_record.property_nft == property_nft.key() @ ErrorCode::InvalidParameter,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+}
+
+pub fn create_property_record(
+    ctx: Context<CreatePropertyRecord>,
+    parcel_hash: [u8; 32],
+    parcel_id: String,
+    property_type: PropertyType,
+    square_feet: u32,
+    year_built: u16,
+    occupancy: Occupancy,
+) -> Result<()> {
+    let property_nft = &mut ctx.accounts.property_nft;
+    let parcel_id = PropertyRecord::normalize_parcel_id(&parcel_id)?;
+
+    // The seed must match the normalized parcel, or the same parcel could be
+    // registered again under a differently formatted id
+    require!(
+        parcel_hash == PropertyRecord::parcel_hash(&property_nft.jurisdiction, &parcel_id),
+        ErrorCode::InvalidParcelId
+    );
+
+    let property_record = &mut ctx.accounts.property_record;
+    property_record.property_nft = property_nft.key();
+    property_record.parcel_id = parcel_id;
+    property_record.jurisdiction = property_nft.jurisdiction;
+    property_record.property_type = property_type;
+    property_record.square_feet = square_feet;
+    property_record.year_built = year_built;
+    property_record.occupancy = occupancy;
+    property_record.documents = Vec::new();
+    property_record.updated_at = Clock::get()?.unix_timestamp;
+    property_record.bump = ctx.bumps.property_record;
+
+    property_nft.record = Some(property_record.key());
+
+    Ok(())
+}
+
+pub fn update_property_record(
+    ctx: Context<UpdatePropertyRecord>,
+    property_type: PropertyType,
+    square_feet: u32,
+    year_built: u16,
+    occupancy: Occupancy,
+) -> Result<()> {
+    let property_record = &mut ctx.accounts.property_record;
+    property_record.property_type = property_type;
+    property_record.square_feet = square_feet;
+    property_record.year_built = year_built;
+    property_record.occupancy = occupancy;
+    property_record.updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+pub fn attach_property_document(
+    ctx: Context<UpdatePropertyRecord>,
+    kind: DocumentKind,
+    hash: [u8; 32],
+) -> Result<()> {
+    let property_record = &mut ctx.accounts.property_record;
+    let clock = Clock::get()?;
+
+    // A newer document replaces the previous one of the same kind
+    match property_record.documents.iter_mut().find(|doc| doc.kind == kind) {
+        Some(document) => {
+            document.hash = hash;
+            document.attached_at = clock.unix_timestamp;
+        }
+        None => {
+            require!(
+                property_record.documents.len() < PropertyRecord::MAX_DOCUMENTS,
+                ErrorCode::TooManyDocuments
+            );
+            property_record.documents.push(PropertyDocument {
+                kind,
+                hash,
+                attached_at: clock.unix_timestamp,
+            });
+        }
+    }
+    property_record.updated_at = clock.unix_timestamp;
+
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::lien_registry::hand_over_lien;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+
+/// Maximum loan-to-value for a refinanced mortgage (basis points)
+pub const MAX_REFINANCE_LTV: u64 = 8_000;
+
+/// Refinance fee charged to the borrower (basis points of the new loan)
+pub const REFINANCE_FEE_BPS: u64 = 50;
+
+#[derive(Accounts)]
+pub struct RefinanceMortgage<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = borrower,
+        constraint = old_mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = old_mortgage.is_active @ ErrorCode::MortgageInactive,
+        constraint = !old_mortgage.is_default @ ErrorCode::MortgageDefaulted,
+    )]
+    pub old_mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        constraint = old_lending_pool.key() == old_mortgage.lending_pool @ ErrorCode::InvalidParameter,
+    )]
+    pub old_lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        constraint = old_pool_vault.key() == old_lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub old_pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = new_lending_pool.key() != old_lending_pool.key() @ ErrorCode::InvalidParameter,
+        constraint = new_lending_pool.is_active @ ErrorCode::PoolInactive,
+    )]
+    pub new_lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: new pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", new_lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: borrower's attestation for the new pool, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = new_pool_vault.key() == new_lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub new_pool_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: new pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub new_pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"borrower_exposure", borrower.key().as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(init, payer = borrower, space = Mortgage::LEN)]
+    pub new_mortgage: Account<'info, Mortgage>,
+
+    /// CHECK: borrower KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == old_mortgage.property_nft @ ErrorCode::InvalidParameter,
+        constraint = property_nft.locked_by == Some(old_mortgage.key()) @ ErrorCode::NFTNotLocked,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    /// CHECK: property lien registry PDA, validated by `hand_over_lien`; uninitialized
+    /// when no liens were ever recorded
+    #[account(mut)]
+    pub lien_registry: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == borrower.key() @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == program_state.treasury @ ErrorCode::InvalidParameter,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn refinance_mortgage(ctx: Context<RefinanceMortgage>) -> Result<()> {
+    let program_state = &ctx.accounts.program_state;
+    let new_lending_pool = &mut ctx.accounts.new_lending_pool;
+    let property_nft = &mut ctx.accounts.property_nft;
+    let clock = Clock::get()?;
+
+    let payoff = ctx.accounts.old_mortgage.remaining_balance;
+
+    enforce_pool_access(
+        &new_lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.borrower.key(),
+        PoolAccess::Borrow,
+    )?;
+    let borrower_jurisdiction = enforce_borrower_identity(
+        new_lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &ctx.accounts.borrower.key(),
+    )?;
+
+    require!(
+        payoff >= program_state.min_loan_amount && payoff <= program_state.max_loan_amount,
+        ErrorCode::InvalidLoanAmount
+    );
+    require!(
+        new_lending_pool.interest_rate >= program_state.min_interest_rate
+            && new_lending_pool.interest_rate <= program_state.max_interest_rate,
+        ErrorCode::InvalidInterestRate
+    );
+    require!(
+        new_lending_pool.loan_duration >= program_state.min_loan_duration
+            && new_lending_pool.loan_duration <= program_state.max_loan_duration,
+        ErrorCode::InvalidLoanDuration
+    );
+    require!(property_nft.property_value > 0, ErrorCode::InvalidParameter);
+    let ltv = (payoff as u128)
+        .checked_mul(10_000)
+        .ok_or(ErrorCode::Overflow)?
+        / property_nft.property_value as u128;
+    require!(ltv <= MAX_REFINANCE_LTV as u128, ErrorCode::LoanToValueTooHigh);
+    ctx.accounts.jurisdiction_rule.check_loan(
+        LoanProduct::Refinance,
+        new_lending_pool.interest_rate,
+        payoff,
+        property_nft.property_value,
+        borrower_jurisdiction,
+    )?;
+    require!(
+        new_lending_pool.available_liquidity() >= payoff,
+        ErrorCode::InsufficientLiquidity
+    );
+    // The new loan replaces the payoff one for one, so the borrower's exposure is
+    // unchanged but must still fit the new pool's cap
+    enforce_loan_limits(
+        new_lending_pool,
+        &ctx.accounts.new_pool_limits,
+        &ctx.accounts.borrower_exposure,
+        payoff,
+        0,
+    )?;
+    let loan_duration = new_lending_pool.loan_duration;
+    new_lending_pool.match_term_liquidity(payoff, loan_duration)?;
+
+    // Fee is paid by the borrower so the new loan exactly matches the payoff
+    let fee = payoff
+        .checked_mul(REFINANCE_FEE_BPS)
+        .ok_or(ErrorCode::Overflow)?
+        / 10_000;
+    if fee > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.borrower_token_account.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.borrower.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    // New pool repays the old pool directly, so no bridge liquidity is needed
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        new_lending_pool.authority.as_ref(),
+        &[new_lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.new_pool_vault.to_account_info(),
+                to: ctx.accounts.old_pool_vault.to_account_info(),
+                authority: new_lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        payoff,
+    )?;
+
+    new_lending_pool.total_borrowed = new_lending_pool.total_borrowed
+        .checked_add(payoff)
+        .ok_or(ErrorCode::Overflow)?;
+    new_lending_pool.last_updated = clock.unix_timestamp;
+
+    let old_lending_pool = &mut ctx.accounts.old_lending_pool;
+    old_lending_pool.total_borrowed = old_lending_pool.total_borrowed.saturating_sub(payoff);
+    old_lending_pool.release_term_liquidity(payoff, ctx.accounts.old_mortgage.loan_duration);
+    old_lending_pool.last_updated = clock.unix_timestamp;
+
+    let months = new_lending_pool.loan_duration / Mortgage::PAYMENT_INTERVAL as u64;
+    let new_mortgage = &mut ctx.accounts.new_mortgage;
+    new_mortgage.borrower = ctx.accounts.borrower.key();
+    new_mortgage.lending_pool = new_lending_pool.key();
+    new_mortgage.property_nft = property_nft.key();
+    new_mortgage.property_nft_mint = property_nft.mint;
+    new_mortgage.loan_amount = payoff;
+    new_mortgage.property_value = property_nft.property_value;
+    new_mortgage.loan_duration = new_lending_pool.loan_duration;
+    new_mortgage.interest_rate = new_lending_pool.interest_rate;
+    new_mortgage.monthly_payment =
+        Mortgage::amortized_payment(payoff, new_lending_pool.interest_rate, months)?;
+    new_mortgage.remaining_balance = payoff;
+    new_mortgage.next_payment_due = clock.unix_timestamp + Mortgage::PAYMENT_INTERVAL;
+    new_mortgage.payments_made = 0;
+    new_mortgage.is_active = true;
+    new_mortgage.is_default = false;
+    new_mortgage.funding_date = clock.unix_timestamp;
+    new_mortgage.close_date = None;
+
+    property_nft.locked_by = Some(new_mortgage.key());
+
+    // The new loan steps into the old one's place in the lien order
+    hand_over_lien(
+        &ctx.accounts.lien_registry,
+        &property_nft.key(),
+        &ctx.accounts.old_mortgage.key(),
+        Some(&ctx.accounts.new_mortgage),
+        false,
+    )?;
+
+    // The old mortgage account is closed by the `close = borrower` constraint
+    let old_mortgage = &mut ctx.accounts.old_mortgage;
+    old_mortgage.remaining_balance = 0;
+    old_mortgage.is_active = false;
+    old_mortgage.close_date = Some(clock.unix_timestamp);
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
+use crate::errors::ErrorCode;
+
 #[account]
 #[derive(Default)]
 pub struct RewardsPool {
@@ -1,5 +1,33 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+/// Appraiser registered by the program authority
+#[account]
+#[derive(Default)]
+pub struct Assessor {
+    /// Assessor wallet that signs risk assessments
+    pub assessor: Pubkey,
+
+    /// Program authority that registered the assessor
+    pub registered_by: Pubkey,
+
+    /// False once the registration has been revoked
+    pub is_active: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Assessor {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // assessor
+                          32 + // registered_by
+                          1 + // is_active
+                          1; // bump
+}
+
 #[account]
 #[derive(Default)]
 pub struct RiskAssessment {
@@ -10,6 +38,7 @@ pub struct RiskAssessment {
     pub assessment_date: i64,
     pub next_assessment_date: i64,
     pub is_valid: bool,
+    pub bump: u8,
 }
 
 impl RiskAssessment {
@@ -20,7 +49,47 @@ impl RiskAssessment {
                           1 + // risk_score
                           8 + // assessment_date
                           8 + // next_assessment_date
-                          1; // is_valid
+                          1 + // is_valid
+                          1; // bump
+}
+
+#[derive(Accounts)]
+pub struct RegisterAssessor<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: wallet being registered as an assessor
+    pub assessor_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Assessor::LEN,
+        seeds = [b"assessor", assessor_wallet.key().as_ref()],
+        bump,
+    )]
+    pub assessor: Account<'info, Assessor>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAssessor<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"assessor", assessor.assessor.as_ref()],
+        bump = assessor.bump,
+    )]
+    pub assessor: Account<'info, Assessor>,
 }
 
 #[derive(Accounts)]
@@ -28,9 +97,27 @@ pub struct CreateRiskAssessment<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    #[account(init, payer = authority, space = RiskAssessment::LEN)]
+    #[account(
+        seeds = [b"assessor", authority.key().as_ref()],
+        bump = assessor.bump,
+        constraint = assessor.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub assessor: Account<'info, Assessor>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RiskAssessment::LEN,
+        seeds = [b"risk_assessment", property_nft.key().as_ref()],
+        bump,
+    )]
     pub risk_assessment: Account<'info, RiskAssessment>,
     
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+    )]
     pub property_nft: Account<'info, PropertyNFT>,
     
     pub system_program: Program<'info, System>,
@@ -42,11 +129,43 @@ pub struct UpdateRiskAssessment<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    /// Any active assessor may reassess or invalidate, so a revoked
+    /// appraiser's assessments can be replaced
+    #[account(
+        seeds = [b"assessor", authority.key().as_ref()],
+        bump = assessor.bump,
+        constraint = assessor.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub assessor: Account<'info, Assessor>,
+    
     #[account(
         mut,
-        constraint = risk_assessment.authority == authority.key()
+        seeds = [b"risk_assessment", property_nft.key().as_ref()],
+        bump = risk_assessment.bump,
     )]
     pub risk_assessment: Account<'info, RiskAssessment>,
+    
+    #[account(
+        mut,
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == risk_assessment.property @ ErrorCode::InvalidParameter
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+}
+
+pub fn register_assessor(ctx: Context<RegisterAssessor>) -> Result<()> {
+    let assessor = &mut ctx.accounts.assessor;
+    assessor.assessor = ctx.accounts.assessor_wallet.key();
+    assessor.registered_by = ctx.accounts.authority.key();
+    assessor.is_active = true;
+    assessor.bump = ctx.bumps.assessor;
+    Ok(())
+}
+
+pub fn revoke_assessor(ctx: Context<RevokeAssessor>) -> Result<()> {
+    ctx.accounts.assessor.is_active = false;
+    Ok(())
 }
 
 pub fn create_risk_assessment(
@@ -64,6 +183,12 @@ pub fn create_risk_assessment(
     risk_assessment.assessment_date = clock.unix_timestamp;
     risk_assessment.next_assessment_date = clock.unix_timestamp + 180 * 24 * 60 * 60; // 180 days
     risk_assessment.is_valid = true;
+    risk_assessment.bump = ctx.bumps.risk_assessment;
+    
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.property_value = appraised_value;
+    property_nft.value_updated_at = clock.unix_timestamp;
+    property_nft.value_current = true;
     
     Ok(())
 }
@@ -76,15 +201,23 @@ pub fn update_risk_assessment(
     let risk_assessment = &mut ctx.accounts.risk_assessment;
     let clock = Clock::get()?;
     
+    // An invalidated assessment can be redone right away
     require!(
-        clock.unix_timestamp >= risk_assessment.next_assessment_date,
+        clock.unix_timestamp >= risk_assessment.next_assessment_date || !risk_assessment.is_valid,
         ErrorCode::AssessmentTooEarly
     );
     
+    risk_assessment.authority = ctx.accounts.authority.key();
     risk_assessment.appraised_value = new_appraised_value;
     risk_assessment.risk_score = new_risk_score;
     risk_assessment.assessment_date = clock.unix_timestamp;
     risk_assessment.next_assessment_date = clock.unix_timestamp + 180 * 24 * 60 * 60;
+    risk_assessment.is_valid = true;
+    
+    let property_nft = &mut ctx.accounts.property_nft;
+    property_nft.property_value = new_appraised_value;
+    property_nft.value_updated_at = clock.unix_timestamp;
+    property_nft.value_current = true;
     
     Ok(())
 }
@@ -92,5 +225,6 @@ pub fn update_risk_assessment(
 pub fn invalidate_risk_assessment(ctx: Context<UpdateRiskAssessment>) -> Result<()> {
     let risk_assessment = &mut ctx.accounts.risk_assessment;
     risk_assessment.is_valid = false;
+    ctx.accounts.property_nft.value_current = false;
     Ok(())
 }
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use std::convert::TryFrom;
 
+use crate::errors::ErrorCode;
+
 /// Global program state account
 #[account]
 #[derive(Default)]
@@ -77,6 +79,18 @@ pub struct LendingPool {
     
     /// Last updated timestamp
     pub last_updated: i64,
+    
+    /// PDA bump; the pool account is the authority of its stablecoin vault
+    pub bump: u8,
+    
+    /// Deposits locked in term positions that cannot be withdrawn before maturity
+    pub committed_term_liquidity: u64,
+    
+    /// Minimum borrower KYC verification level (0 = no attestation required)
+    pub min_verification_level: u8,
+    
+    /// Outstanding long-dated principal funded from committed term liquidity
+    pub term_matched_borrowed: u64,
 }
 
 impl LendingPool {
@@ -88,9 +102,47 @@ impl LendingPool {
                           8 + // total_deposited
                           8 + // total_borrowed
                           1 + // is_active
-                          8; // last_updated
+                          8 + // last_updated
+                          1 + // bump
+                          8 + // committed_term_liquidity
+                          1 + // min_verification_level
+                          8; // term_matched_borrowed
+    
+    /// Loans at least this long must be funded from committed term deposits
+    pub const LONG_DATED_DURATION: u64 = 5 * Mortgage::SECONDS_PER_YEAR;
+    
+    /// Deposits not currently lent out
+    pub fn available_liquidity(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.total_borrowed)
+    }
+    
+    /// Matches a new long-dated loan against committed term liquidity; shorter loans need none
+    pub fn match_term_liquidity(&mut self, amount: u64, loan_duration: u64) -> Result<()> {
+        if loan_duration < Self::LONG_DATED_DURATION {
+            return Ok(());
+        }
+        let matched = self.term_matched_borrowed
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            matched <= self.committed_term_liquidity,
+            ErrorCode::InsufficientTermLiquidity
+        );
+        self.term_matched_borrowed = matched;
+        Ok(())
+    }
+    
+    /// Frees term liquidity as long-dated principal is repaid or written off
+    pub fn release_term_liquidity(&mut self, amount: u64, loan_duration: u64) {
+        if loan_duration >= Self::LONG_DATED_DURATION {
+            self.term_matched_borrowed = self.term_matched_borrowed.saturating_sub(amount);
+        }
+    }
 }
 
+/// ISO 3166-2 style jurisdiction code, zero padded (e.g. `US-CA`)
+pub type JurisdictionCode = [u8; 8];
+
 /// Lender position account tracking deposits
 #[account]
 #[derive(Default)]
@@ -191,6 +243,48 @@ impl Mortgage {
                           1 + // is_default
                           8 + // funding_date
                           9; // Option<i64> close_date (1 byte for option, 8 bytes for i64)
+    
+    /// Seconds between scheduled payments
+    pub const PAYMENT_INTERVAL: i64 = 30 * 24 * 60 * 60;
+    
+    pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+    
+    /// Simple interest on `balance` at `interest_rate` (basis points per year) over `elapsed` seconds
+    pub fn interest_for(balance: u64, interest_rate: u64, elapsed: i64) -> Result<u64> {
+        if elapsed <= 0 {
+            return Ok(0);
+        }
+        let interest = (balance as u128)
+            .checked_mul(interest_rate as u128)
+            .and_then(|v| v.checked_mul(elapsed as u128))
+            .ok_or(ErrorCode::Overflow)?
+            / (10_000u128 * Self::SECONDS_PER_YEAR as u128);
+        u64::try_from(interest).map_err(|_| ErrorCode::Overflow.into())
+    }
+    
+    /// Level monthly payment that amortizes `principal` over `months` payments
+    pub fn amortized_payment(principal: u64, interest_rate: u64, months: u64) -> Result<u64> {
+        require!(months > 0, ErrorCode::InvalidLoanDuration);
+        if interest_rate == 0 {
+            return Ok(principal / months + u64::from(principal % months != 0));
+        }
+        
+        // Fixed point with 12 decimals: payment = P * r * (1 + r)^n / ((1 + r)^n - 1)
+        const SCALE: u128 = 1_000_000_000_000;
+        let monthly_rate = (interest_rate as u128) * SCALE / (10_000 * 12);
+        let mut factor = SCALE;
+        for _ in 0..months {
+            factor = factor
+                .checked_mul(SCALE + monthly_rate)
+                .ok_or(ErrorCode::Overflow)?
+                / SCALE;
+        }
+        let payment = (principal as u128)
+            .checked_mul(monthly_rate * factor / (factor - SCALE))
+            .ok_or(ErrorCode::Overflow)?
+            / SCALE;
+        u64::try_from(payment).map_err(|_| ErrorCode::Overflow.into())
+    }
 }
 
 /// Property NFT metadata account
@@ -206,7 +300,7 @@ pub struct PropertyNFT {
     /// Associated token account holding the NFT
     pub token_account: Pubkey,
     
-    /// Property value in stablecoin units
+    /// Property value in stablecoin units, synced from the latest risk assessment
     pub property_value: u64,
     
     /// Property address (limited to 100 chars)
@@ -220,6 +314,18 @@ pub struct PropertyNFT {
     
     /// Registration date
     pub registration_date: i64,
+    
+    /// Jurisdiction the property is located in (zeroed until tagged)
+    pub jurisdiction: JurisdictionCode,
+    
+    /// Structured property record keyed by parcel, once created
+    pub record: Option<Pubkey>,
+    
+    /// When `property_value` was last set
+    pub value_updated_at: i64,
+    
+    /// False until a valid assessment backs the value, and again after one is invalidated
+    pub value_current: bool,
 }
 
 impl PropertyNFT {
@@ -231,9 +337,43 @@ impl PropertyNFT {
                             4 + // property_address string prefix
                             1 + // is_locked
                             33 + // Option<Pubkey> locked_by (1 byte for option, 32 bytes for Pubkey)
-                            8; // registration_date
+                            8 + // registration_date
+                            8 + // jurisdiction
+                            33 + // Option<Pubkey> record
+                            8 + // value_updated_at
+                            1; // value_current
     
     // Max size includes max address string length (100 chars)
     pub const MAX_LEN: usize = Self::BASE_LEN + 100;
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amortized_payment_matches_closed_form() {
+        // $200,000 at 6% over 30 years is $1,199.10 a month
+        assert_eq!(
+            Mortgage::amortized_payment(200_000_000_000, 600, 360).unwrap(),
+            1_199_101_050
+        );
+        // $10 at 12% over a year is $0.888 a month
+        assert_eq!(Mortgage::amortized_payment(10_000_000, 1_200, 12).unwrap(), 888_487);
+    }
+
+    #[test]
+    fn amortized_payment_at_zero_rate_rounds_up() {
+        assert_eq!(Mortgage::amortized_payment(1_200, 0, 12).unwrap(), 100);
+        assert_eq!(Mortgage::amortized_payment(1_000, 0, 3).unwrap(), 334);
+    }
+
+    #[test]
+    fn amortized_payment_rejects_zero_months() {
+        assert_eq!(
+            Mortgage::amortized_payment(1_000, 600, 0).unwrap_err(),
+            ErrorCode::InvalidLoanDuration.into()
+        );
+    }
+}
@@ -0,0 +1,656 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::compliance::{JurisdictionRule, LoanProduct};
+use crate::errors::ErrorCode;
+use crate::identity::enforce_borrower_identity;
+use crate::pool_limits::{enforce_loan_limits, BorrowerExposure};
+use crate::property_records::PropertyRecord;
+use crate::state::*;
+use crate::title_registry::{TitleAttestation, TitleRegistrar};
+use crate::tranches::apply_tranche_waterfall;
+
+/// Split of a large mortgage across several lending pools
+#[account]
+#[derive(Default)]
+pub struct Syndicate {
+    /// Syndicated mortgage
+    pub mortgage: Pubkey,
+
+    /// Participating pools and their fixed shares
+    pub participations: Vec<Participation>,
+
+    /// Number of participations that have funded their share
+    pub funded_count: u8,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Participation {
+    /// Participating lending pool
+    pub lending_pool: Pubkey,
+
+    /// Participation share (basis points of the loan)
+    pub share_bps: u16,
+
+    /// Amount this pool disbursed
+    pub funded_amount: u64,
+
+    /// Principal repaid to this pool
+    pub principal_repaid: u64,
+
+    /// Interest paid to this pool
+    pub interest_received: u64,
+
+    /// Liquidation proceeds paid to this pool
+    pub proceeds_received: u64,
+
+    /// True once this pool has written off its share of a defaulted loan
+    pub written_off: bool,
+}
+
+impl Participation {
+    pub const LEN: usize = 32 + // lending_pool
+                          2 + // share_bps
+                          8 + // funded_amount
+                          8 + // principal_repaid
+                          8 + // interest_received
+                          8 + // proceeds_received
+                          1; // written_off
+}
+
+impl Syndicate {
+    pub const MAX_PARTICIPANTS: usize = 5;
+
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // mortgage
+                          4 + Self::MAX_PARTICIPANTS * Participation::LEN + // participations
+                          1 + // funded_count
+                          1; // bump
+
+    pub fn is_fully_funded(&self) -> bool {
+        self.funded_count as usize == self.participations.len()
+    }
+
+    /// Splits `amount` by participation share; rounding dust goes to the last participant
+    pub fn split(&self, amount: u64) -> Result<Vec<u64>> {
+        let mut shares = Vec::with_capacity(self.participations.len());
+        let mut allocated: u64 = 0;
+        for participation in self.participations.iter() {
+            let share = (amount as u128)
+                .checked_mul(participation.share_bps as u128)
+                .ok_or(ErrorCode::Overflow)?
+                / 10_000;
+            let share = u64::try_from(share).map_err(|_| ErrorCode::Overflow)?;
+            allocated = allocated.checked_add(share).ok_or(ErrorCode::Overflow)?;
+            shares.push(share);
+        }
+        if let Some(last) = shares.last_mut() {
+            *last = last
+                .checked_add(amount.checked_sub(allocated).ok_or(ErrorCode::Overflow)?)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        Ok(shares)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateSyndicate<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        constraint = mortgage.borrower == borrower.key() @ ErrorCode::Unauthorized,
+        constraint = !mortgage.is_active @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = Syndicate::LEN,
+        seeds = [b"syndicate", mortgage.key().as_ref()],
+        bump,
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundParticipation<'info> {
+    pub pool_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = lending_pool.authority == pool_authority.key() @ ErrorCode::Unauthorized,
+        constraint = lending_pool.is_active @ ErrorCode::PoolInactive,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: borrower's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: borrower KYC attestation, validated by `enforce_borrower_identity`
+    pub borrower_identity: UncheckedAccount<'info>,
+
+    /// CHECK: KYC provider that issued the identity, validated by `enforce_borrower_identity`
+    pub identity_provider: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_loan_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", mortgage.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        constraint = !mortgage.is_active @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.key() == mortgage.property_nft @ ErrorCode::InvalidParameter,
+        constraint = property_nft.locked_by == Some(mortgage.key()) @ ErrorCode::NFTNotLocked,
+        constraint = property_nft.value_current @ ErrorCode::InvalidAssessment,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        seeds = [b"property_record", property_record.seed_hash().as_ref()],
+        bump = property_record.bump,
+        constraint = property_nft.record == Some(property_record.key()) @ ErrorCode::PropertyRecordMissing,
+        constraint = property_record.describes(&property_nft) @ ErrorCode::PropertyRecordMissing,
+    )]
+    pub property_record: Account<'info, PropertyRecord>,
+
+    #[account(
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump = title_attestation.bump,
+        constraint = title_attestation.is_current(&property_nft, &title_registrar) @ ErrorCode::TitleNotCurrent,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    #[account(
+        seeds = [b"title_registrar", title_attestation.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        seeds = [b"jurisdiction_rule", property_nft.jurisdiction.as_ref()],
+        bump = jurisdiction_rule.bump,
+    )]
+    pub jurisdiction_rule: Account<'info, JurisdictionRule>,
+
+    #[account(
+        mut,
+        seeds = [b"syndicate", mortgage.key().as_ref()],
+        bump = syndicate.bump,
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == mortgage.borrower @ ErrorCode::Unauthorized,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Remaining accounts: `(lending_pool, stablecoin_vault, pool_tranches)` triples in
+/// participation order
+#[derive(Accounts)]
+pub struct DistributeSyndicated<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", mortgage.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        seeds = [b"syndicate", mortgage.key().as_ref()],
+        bump = syndicate.bump,
+        constraint = syndicate.is_fully_funded() @ ErrorCode::SyndicateNotFunded,
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Remaining accounts: `(lending_pool, stablecoin_vault, pool_tranches)` triples in
+/// participation order
+#[derive(Accounts)]
+pub struct DistributeSyndicatedProceeds<'info> {
+    pub authority: Signer<'info>,
+
+    /// Lead pool of the syndicate, whose authority runs the liquidation
+    #[account(
+        constraint = lending_pool.key() == mortgage.lending_pool @ ErrorCode::InvalidParameter,
+        constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        constraint = mortgage.is_active @ ErrorCode::MortgageInactive,
+        constraint = mortgage.is_default @ ErrorCode::InvalidParameter,
+    )]
+    pub mortgage: Account<'info, Mortgage>,
+
+    #[account(
+        mut,
+        seeds = [b"borrower_exposure", mortgage.borrower.as_ref()],
+        bump = borrower_exposure.bump,
+    )]
+    pub borrower_exposure: Account<'info, BorrowerExposure>,
+
+    #[account(
+        mut,
+        seeds = [b"syndicate", mortgage.key().as_ref()],
+        bump = syndicate.bump,
+        constraint = syndicate.is_fully_funded() @ ErrorCode::SyndicateNotFunded,
+    )]
+    pub syndicate: Account<'info, Syndicate>,
+
+    /// Account holding the sale proceeds of the seized property
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Borrower's account receiving any surplus over the outstanding balance
+    #[account(
+        mut,
+        constraint = borrower_token_account.owner == mortgage.borrower @ ErrorCode::InvalidParameter,
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_syndicate(
+    ctx: Context<CreateSyndicate>,
+    lending_pools: Vec<Pubkey>,
+    shares_bps: Vec<u16>,
+) -> Result<()> {
+    require!(
+        !lending_pools.is_empty()
+            && lending_pools.len() <= Syndicate::MAX_PARTICIPANTS
+            && lending_pools.len() == shares_bps.len(),
+        ErrorCode::InvalidParticipations
+    );
+    let total_bps: u32 = shares_bps.iter().map(|share| *share as u32).sum();
+    require!(total_bps == 10_000, ErrorCode::InvalidParticipations);
+    require!(
+        shares_bps.iter().all(|share| *share > 0),
+        ErrorCode::InvalidParticipations
+    );
+    for (index, pool) in lending_pools.iter().enumerate() {
+        require!(
+            !lending_pools[..index].contains(pool),
+            ErrorCode::InvalidParticipations
+        );
+    }
+
+    let syndicate = &mut ctx.accounts.syndicate;
+    syndicate.mortgage = ctx.accounts.mortgage.key();
+    syndicate.participations = lending_pools
+        .into_iter()
+        .zip(shares_bps)
+        .map(|(lending_pool, share_bps)| Participation {
+            lending_pool,
+            share_bps,
+            ..Participation::default()
+        })
+        .collect();
+    syndicate.funded_count = 0;
+    syndicate.bump = ctx.bumps.syndicate;
+
+    Ok(())
+}
+
+pub fn fund_participation(ctx: Context<FundParticipation>) -> Result<()> {
+    let syndicate = &mut ctx.accounts.syndicate;
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let mortgage = &mut ctx.accounts.mortgage;
+    let clock = Clock::get()?;
+
+    let index = syndicate
+        .participations
+        .iter()
+        .position(|participation| participation.lending_pool == lending_pool.key())
+        .ok_or(ErrorCode::InvalidParticipations)?;
+    require!(
+        syndicate.participations[index].funded_amount == 0,
+        ErrorCode::ParticipationAlreadyFunded
+    );
+
+    enforce_pool_access(
+        &lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &mortgage.borrower,
+        PoolAccess::Borrow,
+    )?;
+    let borrower_jurisdiction = enforce_borrower_identity(
+        lending_pool,
+        &ctx.accounts.borrower_identity,
+        &ctx.accounts.identity_provider,
+        &mortgage.borrower,
+    )?;
+    ctx.accounts.jurisdiction_rule.check_loan(
+        LoanProduct::Mortgage,
+        mortgage.interest_rate,
+        mortgage.loan_amount,
+        ctx.accounts.property_nft.property_value,
+        borrower_jurisdiction,
+    )?;
+
+    let amount = syndicate.split(mortgage.loan_amount)?[index];
+    require!(
+        lending_pool.available_liquidity() >= amount,
+        ErrorCode::InsufficientLiquidity
+    );
+    // Each pool underwrites its own share against its caps and the borrower's total exposure
+    enforce_loan_limits(
+        lending_pool,
+        &ctx.accounts.pool_limits,
+        &ctx.accounts.borrower_exposure,
+        amount,
+        amount,
+    )?;
+    lending_pool.match_term_liquidity(amount, mortgage.loan_duration)?;
+
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        lending_pool.authority.as_ref(),
+        &[lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stablecoin_vault.to_account_info(),
+                to: ctx.accounts.borrower_token_account.to_account_info(),
+                authority: lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    lending_pool.total_borrowed = lending_pool.total_borrowed
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+    ctx.accounts.borrower_exposure.add(amount)?;
+
+    syndicate.participations[index].funded_amount = amount;
+    syndicate.funded_count += 1;
+
+    // The last participation to fund activates the mortgage
+    if syndicate.is_fully_funded() {
+        let months = mortgage.loan_duration / Mortgage::PAYMENT_INTERVAL as u64;
+        mortgage.monthly_payment =
+            Mortgage::amortized_payment(mortgage.loan_amount, mortgage.interest_rate, months)?;
+        mortgage.remaining_balance = mortgage.loan_amount;
+        mortgage.next_payment_due = clock.unix_timestamp + Mortgage::PAYMENT_INTERVAL;
+        mortgage.payments_made = 0;
+        mortgage.is_active = true;
+        mortgage.is_default = false;
+        mortgage.funding_date = clock.unix_timestamp;
+    }
+
+    Ok(())
+}
+
+pub fn make_syndicated_payment<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeSyndicated<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let mortgage = &mut ctx.accounts.mortgage;
+    let syndicate = &mut ctx.accounts.syndicate;
+    let clock = Clock::get()?;
+
+    require!(mortgage.is_active, ErrorCode::MortgageInactive);
+    require!(!mortgage.is_default, ErrorCode::MortgageDefaulted);
+    require!(
+        amount >= mortgage.monthly_payment.min(mortgage.remaining_balance),
+        ErrorCode::InsufficientPayment
+    );
+
+    let interest_due = Mortgage::interest_for(
+        mortgage.remaining_balance,
+        mortgage.interest_rate,
+        Mortgage::PAYMENT_INTERVAL,
+    )?;
+    let interest = amount.min(interest_due);
+    let principal = (amount - interest).min(mortgage.remaining_balance);
+
+    let interest_shares = syndicate.split(interest)?;
+    let principal_shares = syndicate.split(principal)?;
+
+    for (index, participation) in syndicate.participations.iter_mut().enumerate() {
+        let (mut lending_pool, vault, pool_tranches) =
+            participant_accounts(ctx.remaining_accounts, index, participation)?;
+        let share = interest_shares[index] + principal_shares[index];
+
+        if share > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.payer_token_account.to_account_info(),
+                        to: vault,
+                        authority: ctx.accounts.payer.to_account_info(),
+                    },
+                ),
+                share,
+            )?;
+        }
+
+        lending_pool.total_borrowed = lending_pool.total_borrowed
+            .saturating_sub(principal_shares[index]);
+        lending_pool.release_term_liquidity(principal_shares[index], mortgage.loan_duration);
+        lending_pool.total_deposited = lending_pool.total_deposited
+            .checked_add(interest_shares[index])
+            .ok_or(ErrorCode::Overflow)?;
+        lending_pool.last_updated = clock.unix_timestamp;
+        lending_pool.exit(&crate::ID)?;
+        apply_tranche_waterfall(&lending_pool.key(), &pool_tranches, interest_shares[index], 0)?;
+
+        participation.principal_repaid = participation.principal_repaid
+            .checked_add(principal_shares[index])
+            .ok_or(ErrorCode::Overflow)?;
+        participation.interest_received = participation.interest_received
+            .checked_add(interest_shares[index])
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    mortgage.remaining_balance -= principal;
+    ctx.accounts.borrower_exposure.sub(principal);
+    mortgage.payments_made += 1;
+    mortgage.next_payment_due = mortgage.next_payment_due
+        .checked_add(Mortgage::PAYMENT_INTERVAL)
+        .ok_or(ErrorCode::Overflow)?;
+    if mortgage.remaining_balance == 0 {
+        mortgage.is_active = false;
+        mortgage.close_date = Some(clock.unix_timestamp);
+    }
+
+    Ok(())
+}
+
+pub fn distribute_syndicated_proceeds<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeSyndicatedProceeds<'info>>,
+    proceeds: u64,
+) -> Result<()> {
+    let mortgage = &mut ctx.accounts.mortgage;
+    let syndicate = &mut ctx.accounts.syndicate;
+    let clock = Clock::get()?;
+
+    require!(proceeds > 0, ErrorCode::InvalidParameter);
+
+    // Pools recover up to the outstanding balance; the rest goes to the borrower
+    let recovered = proceeds.min(mortgage.remaining_balance);
+    let surplus = proceeds - recovered;
+    let outstanding = syndicate.split(mortgage.remaining_balance)?;
+    let shares = syndicate.split(recovered)?;
+
+    for (index, participation) in syndicate.participations.iter_mut().enumerate() {
+        require!(!participation.written_off, ErrorCode::ParticipationWrittenOff);
+        let (mut lending_pool, vault, pool_tranches) =
+            participant_accounts(ctx.remaining_accounts, index, participation)?;
+        let share = shares[index];
+
+        if share > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.authority_token_account.to_account_info(),
+                        to: vault,
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                share,
+            )?;
+        }
+
+        // Each pool clears its share of the remaining balance and books the shortfall as a loss
+        let loss = outstanding[index].saturating_sub(share);
+        lending_pool.total_borrowed = lending_pool.total_borrowed
+            .saturating_sub(outstanding[index]);
+        lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(loss);
+        lending_pool.release_term_liquidity(outstanding[index], mortgage.loan_duration);
+        lending_pool.last_updated = clock.unix_timestamp;
+        lending_pool.exit(&crate::ID)?;
+        apply_tranche_waterfall(&lending_pool.key(), &pool_tranches, 0, loss)?;
+
+        participation.proceeds_received = participation.proceeds_received
+            .checked_add(share)
+            .ok_or(ErrorCode::Overflow)?;
+        participation.written_off = true;
+    }
+
+    if surplus > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.borrower_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            surplus,
+        )?;
+    }
+
+    ctx.accounts.borrower_exposure.sub(mortgage.remaining_balance);
+    mortgage.remaining_balance = 0;
+    mortgage.is_active = false;
+    mortgage.close_date = Some(clock.unix_timestamp);
+
+    Ok(())
+}
+
+fn participant_accounts<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    index: usize,
+    participation: &Participation,
+) -> Result<(Account<'info, LendingPool>, AccountInfo<'info>, AccountInfo<'info>)> {
+    let pool_info = remaining_accounts
+        .get(index * 3)
+        .ok_or(ErrorCode::InvalidParticipations)?;
+    let vault_info = remaining_accounts
+        .get(index * 3 + 1)
+        .ok_or(ErrorCode::InvalidParticipations)?;
+    let tranches_info = remaining_accounts
+        .get(index * 3 + 2)
+        .ok_or(ErrorCode::InvalidParticipations)?;
+
+    let lending_pool = Account::<LendingPool>::try_from(pool_info)?;
+    require_keys_eq!(
+        lending_pool.key(),
+        participation.lending_pool,
+        ErrorCode::InvalidParticipations
+    );
+    require_keys_eq!(
+        vault_info.key(),
+        lending_pool.stablecoin_vault,
+        ErrorCode::InvalidParticipations
+    );
+
+    Ok((lending_pool, vault_info.clone(), tranches_info.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syndicate(shares_bps: &[u16]) -> Syndicate {
+        Syndicate {
+            participations: shares_bps
+                .iter()
+                .map(|&share_bps| Participation {
+                    share_bps,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn split_is_pro_rata() {
+        let syndicate = syndicate(&[6_000, 3_000, 1_000]);
+        assert_eq!(syndicate.split(1_000).unwrap(), vec![600, 300, 100]);
+    }
+
+    #[test]
+    fn split_gives_dust_to_last_participant() {
+        let syndicate = syndicate(&[3_333, 3_333, 3_334]);
+        let shares = syndicate.split(100).unwrap();
+        assert_eq!(shares, vec![33, 33, 34]);
+        assert_eq!(shares.iter().sum::<u64>(), 100);
+    }
+}
@@ -0,0 +1,440 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::errors::ErrorCode;
+use crate::pool_limits::enforce_deposit_limits;
+use crate::state::*;
+
+/// Fixed-term lender deposit that cannot be withdrawn before maturity
+#[account]
+#[derive(Default)]
+pub struct TermDeposit {
+    /// Owner of the deposit
+    pub owner: Pubkey,
+
+    /// Lending pool holding the funds
+    pub lending_pool: Pubkey,
+
+    /// Amount deposited
+    pub amount: u64,
+
+    /// Term length in years
+    pub term_years: u8,
+
+    /// Yield boost earned for the lock (basis points per year)
+    pub boost_rate: u64,
+
+    /// Deposit date
+    pub start_date: i64,
+
+    /// Date the deposit can be redeemed
+    pub maturity_date: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TermDeposit {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // owner
+                          32 + // lending_pool
+                          8 + // amount
+                          1 + // term_years
+                          8 + // boost_rate
+                          8 + // start_date
+                          8 + // maturity_date
+                          1; // bump
+
+    /// Supported terms and their yield boosts (basis points per year)
+    pub const TERMS: [(u8, u64); 3] = [(1, 100), (3, 250), (5, 400)];
+
+    pub fn boost_for(term_years: u8) -> Option<u64> {
+        Self::TERMS
+            .iter()
+            .find(|(years, _)| *years == term_years)
+            .map(|(_, boost)| *boost)
+    }
+
+    /// Boost earned over the full term, paid from the pool's boost reserve
+    pub fn boost(&self) -> Result<u64> {
+        Mortgage::interest_for(
+            self.amount,
+            self.boost_rate,
+            self.maturity_date - self.start_date,
+        )
+    }
+}
+
+/// Pool-funded reserve that pays term deposit boosts.
+/// Boosts are reserved in full when a deposit opens, so every open deposit is covered.
+#[account]
+#[derive(Default)]
+pub struct TermBoostReserve {
+    /// Lending pool the reserve belongs to
+    pub lending_pool: Pubkey,
+
+    /// Token account holding the reserve
+    pub vault: Pubkey,
+
+    /// Reserve funds not yet paid out
+    pub funded: u64,
+
+    /// Boosts owed to open term deposits
+    pub committed: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TermBoostReserve {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // lending_pool
+                          32 + // vault
+                          8 + // funded
+                          8 + // committed
+                          1; // bump
+}
+
+#[derive(Accounts)]
+pub struct InitializeTermBoostReserve<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(constraint = stablecoin_mint.key() == stablecoin_vault.mint @ ErrorCode::InvalidParameter)]
+    pub stablecoin_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TermBoostReserve::LEN,
+        seeds = [b"term_boost_reserve", lending_pool.key().as_ref()],
+        bump,
+    )]
+    pub term_boost_reserve: Account<'info, TermBoostReserve>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"term_boost_vault", lending_pool.key().as_ref()],
+        bump,
+        token::mint = stablecoin_mint,
+        token::authority = term_boost_reserve,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundTermBoostReserve<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"term_boost_reserve", term_boost_reserve.lending_pool.as_ref()],
+        bump = term_boost_reserve.bump,
+    )]
+    pub term_boost_reserve: Account<'info, TermBoostReserve>,
+
+    #[account(
+        mut,
+        constraint = reserve_vault.key() == term_boost_reserve.vault @ ErrorCode::InvalidParameter,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ ErrorCode::Unauthorized,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(deposit_id: u64)]
+pub struct OpenTermDeposit<'info> {
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    #[account(mut, constraint = lending_pool.is_active @ ErrorCode::PoolInactive)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_deposit_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = lender,
+        space = TermDeposit::LEN,
+        seeds = [
+            b"term_deposit",
+            lending_pool.key().as_ref(),
+            lender.key().as_ref(),
+            &deposit_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub term_deposit: Account<'info, TermDeposit>,
+
+    #[account(
+        mut,
+        seeds = [b"term_boost_reserve", lending_pool.key().as_ref()],
+        bump = term_boost_reserve.bump,
+    )]
+    pub term_boost_reserve: Account<'info, TermBoostReserve>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.owner == lender.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemTermDeposit<'info> {
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    #[account(mut)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        close = lender,
+        constraint = term_deposit.owner == lender.key() @ ErrorCode::Unauthorized,
+        constraint = term_deposit.lending_pool == lending_pool.key() @ ErrorCode::InvalidParameter,
+    )]
+    pub term_deposit: Account<'info, TermDeposit>,
+
+    #[account(
+        mut,
+        seeds = [b"term_boost_reserve", lending_pool.key().as_ref()],
+        bump = term_boost_reserve.bump,
+    )]
+    pub term_boost_reserve: Account<'info, TermBoostReserve>,
+
+    #[account(
+        mut,
+        constraint = reserve_vault.key() == term_boost_reserve.vault @ ErrorCode::InvalidParameter,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.owner == lender.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_term_boost_reserve(ctx: Context<InitializeTermBoostReserve>) -> Result<()> {
+    let term_boost_reserve = &mut ctx.accounts.term_boost_reserve;
+    term_boost_reserve.lending_pool = ctx.accounts.lending_pool.key();
+    term_boost_reserve.vault = ctx.accounts.reserve_vault.key();
+    term_boost_reserve.funded = 0;
+    term_boost_reserve.committed = 0;
+    term_boost_reserve.bump = ctx.bumps.term_boost_reserve;
+    Ok(())
+}
+
+pub fn fund_term_boost_reserve(ctx: Context<FundTermBoostReserve>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidParameter);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                to: ctx.accounts.reserve_vault.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let term_boost_reserve = &mut ctx.accounts.term_boost_reserve;
+    term_boost_reserve.funded = term_boost_reserve.funded
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
+
+pub fn open_term_deposit(
+    ctx: Context<OpenTermDeposit>,
+    _deposit_id: u64,
+    term_years: u8,
+    amount: u64,
+) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.lender.key(),
+        PoolAccess::Deposit,
+    )?;
+
+    require!(amount > 0, ErrorCode::InvalidParameter);
+    let boost_rate = TermDeposit::boost_for(term_years).ok_or(ErrorCode::InvalidDepositTerm)?;
+    // Each term deposit is its own account, so the per-lender cap applies per deposit
+    enforce_deposit_limits(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.pool_limits,
+        0,
+        amount,
+    )?;
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let term_deposit = &mut ctx.accounts.term_deposit;
+    let clock = Clock::get()?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.lender_token_account.to_account_info(),
+                to: ctx.accounts.stablecoin_vault.to_account_info(),
+                authority: ctx.accounts.lender.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    term_deposit.owner = ctx.accounts.lender.key();
+    term_deposit.lending_pool = lending_pool.key();
+    term_deposit.amount = amount;
+    term_deposit.term_years = term_years;
+    term_deposit.boost_rate = boost_rate;
+    term_deposit.start_date = clock.unix_timestamp;
+    term_deposit.maturity_date = clock.unix_timestamp
+        + term_years as i64 * Mortgage::SECONDS_PER_YEAR as i64;
+    term_deposit.bump = ctx.bumps.term_deposit;
+
+    // The full-term boost must already be funded before the deposit is accepted
+    let boost = term_deposit.boost()?;
+    let term_boost_reserve = &mut ctx.accounts.term_boost_reserve;
+    let committed = term_boost_reserve.committed
+        .checked_add(boost)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(
+        committed <= term_boost_reserve.funded,
+        ErrorCode::InsufficientBoostReserve
+    );
+    term_boost_reserve.committed = committed;
+
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.committed_term_liquidity = lending_pool.committed_term_liquidity
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn redeem_term_deposit(ctx: Context<RedeemTermDeposit>) -> Result<()> {
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let term_deposit = &ctx.accounts.term_deposit;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= term_deposit.maturity_date,
+        ErrorCode::DepositNotMatured
+    );
+
+    let amount = term_deposit.amount;
+    let boost = term_deposit.boost()?;
+    require!(
+        lending_pool.available_liquidity() >= amount,
+        ErrorCode::InsufficientLiquidity
+    );
+
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        lending_pool.authority.as_ref(),
+        &[lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stablecoin_vault.to_account_info(),
+                to: ctx.accounts.lender_token_account.to_account_info(),
+                authority: lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    let term_boost_reserve = &mut ctx.accounts.term_boost_reserve;
+    if boost > 0 {
+        let reserve_seeds = &[
+            b"term_boost_reserve".as_ref(),
+            term_boost_reserve.lending_pool.as_ref(),
+            &[term_boost_reserve.bump],
+        ];
+        let reserve_signer = &[&reserve_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.reserve_vault.to_account_info(),
+                    to: ctx.accounts.lender_token_account.to_account_info(),
+                    authority: term_boost_reserve.to_account_info(),
+                },
+                reserve_signer,
+            ),
+            boost,
+        )?;
+    }
+    term_boost_reserve.funded = term_boost_reserve.funded.saturating_sub(boost);
+    term_boost_reserve.committed = term_boost_reserve.committed.saturating_sub(boost);
+
+    lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(amount);
+    lending_pool.committed_term_liquidity = lending_pool.committed_term_liquidity
+        .saturating_sub(amount);
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    Ok(())
+}
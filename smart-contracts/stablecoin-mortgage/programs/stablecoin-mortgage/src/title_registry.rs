@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+/// Title company or notary approved by the program authority
+#[account]
+#[derive(Default)]
+pub struct TitleRegistrar {
+    /// Registrar wallet that signs title attestations
+    pub registrar: Pubkey,
+
+    /// Program authority that approved the registrar
+    pub registered_by: Pubkey,
+
+    /// False once the approval has been revoked
+    pub is_active: bool,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TitleRegistrar {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // registrar
+                          32 + // registered_by
+                          1 + // is_active
+                          1; // bump
+}
+
+/// Registrar's attestation of who holds title to a property
+#[account]
+#[derive(Default)]
+pub struct TitleAttestation {
+    /// Property NFT the attestation covers
+    pub property_nft: Pubkey,
+
+    /// Registrar that last attested or recorded an event
+    pub registrar: Pubkey,
+
+    /// Owner of record
+    pub owner: Pubkey,
+
+    /// Hash of the title evidence (deed, title search)
+    pub evidence_hash: [u8; 32],
+
+    /// True while a lis pendens is recorded against the property
+    pub lis_pendens: bool,
+
+    /// True once the attestation has been revoked
+    pub revoked: bool,
+
+    /// Original attestation date
+    pub attested_at: i64,
+
+    /// Date of the last recorded title event
+    pub updated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TitleAttestation {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // property_nft
+                          32 + // registrar
+                          32 + // owner
+                          32 + // evidence_hash
+                          1 + // lis_pendens
+                          1 + // revoked
+                          8 + // attested_at
+                          8 + // updated_at
+                          1; // bump
+
+    /// True if the title is unencumbered, the owner of record holds the NFT and the
+    /// registrar behind the attestation is still approved
+    pub fn is_current(&self, property_nft: &PropertyNFT, registrar: &TitleRegistrar) -> bool {
+        self.records_owner(&property_nft.owner, registrar)
+    }
+
+    /// True if the title is unencumbered, names `owner` as owner of record and the
+    /// registrar behind the attestation is still approved
+    pub fn records_owner(&self, owner: &Pubkey, registrar: &TitleRegistrar) -> bool {
+        registrar.is_active
+            && self.registrar == registrar.registrar
+            && !self.revoked
+            && !self.lis_pendens
+            && self.owner == *owner
+    }
+
+    pub fn attest(
+        &mut self,
+        property_nft: Pubkey,
+        registrar: Pubkey,
+        owner: Pubkey,
+        evidence_hash: [u8; 32],
+        bump: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.property_nft = property_nft;
+        self.registrar = registrar;
+        self.owner = owner;
+        self.evidence_hash = evidence_hash;
+        self.lis_pendens = false;
+        self.revoked = false;
+        self.attested_at = clock.unix_timestamp;
+        self.updated_at = clock.unix_timestamp;
+        self.bump = bump;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TitleEvent {
+    /// Title passed to a new owner
+    Transfer { new_owner: Pubkey },
+    /// Pending litigation recorded against the property
+    LisPendens,
+    /// Lis pendens or other encumbrance released
+    Release,
+    /// Attestation withdrawn by the registrar
+    Revoke,
+}
+
+#[event]
+pub struct TitleEventRecorded {
+    pub property_nft: Pubkey,
+    pub registrar: Pubkey,
+    pub event: TitleEvent,
+    pub evidence_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct RegisterTitleRegistrar<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// CHECK: wallet being approved as a title registrar
+    pub registrar_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TitleRegistrar::LEN,
+        seeds = [b"title_registrar", registrar_wallet.key().as_ref()],
+        bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeTitleRegistrar<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program_state.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [b"title_registrar", title_registrar.registrar.as_ref()],
+        bump = title_registrar.bump,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+}
+
+#[derive(Accounts)]
+pub struct AttestTitle<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub registrar: Signer<'info>,
+
+    #[account(
+        seeds = [b"title_registrar", registrar.key().as_ref()],
+        bump = title_registrar.bump,
+        constraint = title_registrar.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        seeds = [b"property_nft", property_nft.mint.as_ref()],
+        bump,
+        constraint = property_nft.owner == owner.key() @ ErrorCode::InvalidNFTOwner,
+    )]
+    pub property_nft: Account<'info, PropertyNFT>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = TitleAttestation::LEN,
+        seeds = [b"title_attestation", property_nft.key().as_ref()],
+        bump,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RecordTitleEvent<'info> {
+    pub registrar: Signer<'info>,
+
+    #[account(
+        seeds = [b"title_registrar", registrar.key().as_ref()],
+        bump = title_registrar.bump,
+        constraint = title_registrar.is_active @ ErrorCode::Unauthorized,
+    )]
+    pub title_registrar: Account<'info, TitleRegistrar>,
+
+    #[account(
+        mut,
+        seeds = [b"title_attestation", title_attestation.property_nft.as_ref()],
+        bump = title_attestation.bump,
+    )]
+    pub title_attestation: Account<'info, TitleAttestation>,
+}
+
+pub fn register_title_registrar(ctx: Context<RegisterTitleRegistrar>) -> Result<()> {
+    let title_registrar = &mut ctx.accounts.title_registrar;
+    title_registrar.registrar = ctx.accounts.registrar_wallet.key();
+    title_registrar.registered_by = ctx.accounts.authority.key();
+    title_registrar.is_active = true;
+    title_registrar.bump = ctx.bumps.title_registrar;
+    Ok(())
+}
+
+pub fn revoke_title_registrar(ctx: Context<RevokeTitleRegistrar>) -> Result<()> {
+    ctx.accounts.title_registrar.is_active = false;
+    Ok(())
+}
+
+/// Attests title for a property registered without a registrar co-signature
+pub fn attest_title(ctx: Context<AttestTitle>, evidence_hash: [u8; 32]) -> Result<()> {
+    ctx.accounts.title_attestation.attest(
+        ctx.accounts.property_nft.key(),
+        ctx.accounts.registrar.key(),
+        ctx.accounts.owner.key(),
+        evidence_hash,
+        ctx.bumps.title_attestation,
+    )
+}
+
+pub fn record_title_event(
+    ctx: Context<RecordTitleEvent>,
+    event: TitleEvent,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let title_attestation = &mut ctx.accounts.title_attestation;
+    require!(!title_attestation.revoked, ErrorCode::TitleNotCurrent);
+
+    match event {
+        TitleEvent::Transfer { new_owner } => title_attestation.owner = new_owner,
+        TitleEvent::LisPendens => title_attestation.lis_pendens = true,
+        TitleEvent::Release => title_attestation.lis_pendens = false,
+        TitleEvent::Revoke => title_attestation.revoked = true,
+    }
+    title_attestation.registrar = ctx.accounts.registrar.key();
+    title_attestation.evidence_hash = evidence_hash;
+    title_attestation.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(TitleEventRecorded {
+        property_nft: title_attestation.property_nft,
+        registrar: ctx.accounts.registrar.key(),
+        event,
+        evidence_hash,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,512 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::allowlist::{enforce_pool_access, PoolAccess};
+use crate::errors::ErrorCode;
+use crate::pool_limits::enforce_deposit_limits;
+use crate::state::*;
+use crate::withdrawal_queue::require_withdrawal_queue_empty;
+
+/// Senior/junior tranche accounting for a lending pool
+#[account]
+#[derive(Default)]
+pub struct PoolTranches {
+    /// Lending pool the tranches belong to
+    pub lending_pool: Pubkey,
+
+    /// Senior tranche: capped yield, first claim on repayments
+    pub senior: Tranche,
+
+    /// Junior tranche: first loss, residual yield
+    pub junior: Tranche,
+
+    /// Maximum annual yield paid to the senior tranche (basis points)
+    pub senior_yield_cap: u64,
+
+    /// Timestamp of the last interest distribution
+    pub last_distribution: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Tranche {
+    /// Principal deposited net of withdrawals
+    pub principal: u64,
+
+    /// Interest credited to the tranche
+    pub earnings: u64,
+
+    /// Losses absorbed by the tranche
+    pub losses: u64,
+
+    /// Outstanding shares
+    pub total_shares: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TrancheKind {
+    Senior,
+    Junior,
+}
+
+impl Tranche {
+    pub const LEN: usize = 8 + // principal
+                          8 + // earnings
+                          8 + // losses
+                          8; // total_shares
+
+    /// Current claim of the tranche on pool assets
+    pub fn value(&self) -> u64 {
+        self.principal
+            .saturating_add(self.earnings)
+            .saturating_sub(self.losses)
+    }
+
+    fn shares_for(&self, amount: u64) -> Result<u64> {
+        if self.total_shares == 0 {
+            return Ok(amount);
+        }
+        // Outstanding shares of a wiped-out tranche are worthless; pricing new money
+        // against them would hand the new deposit to the old holders
+        let value = self.value();
+        require!(value > 0, ErrorCode::TrancheWrittenDown);
+        let shares = (amount as u128)
+            .checked_mul(self.total_shares as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / value as u128;
+        u64::try_from(shares).map_err(|_| ErrorCode::Overflow.into())
+    }
+
+    pub fn amount_for(&self, shares: u64) -> Result<u64> {
+        require!(self.total_shares > 0, ErrorCode::InvalidParameter);
+        let amount = (shares as u128)
+            .checked_mul(self.value() as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / self.total_shares as u128;
+        u64::try_from(amount).map_err(|_| ErrorCode::Overflow.into())
+    }
+
+    /// Burns `shares` and scales the tranche's books down pro rata
+    pub fn redeem(&mut self, shares: u64) -> Result<u64> {
+        let amount = self.amount_for(shares)?;
+        let total = self.total_shares as u128;
+        let remaining = total - shares as u128;
+        self.principal = (self.principal as u128 * remaining / total) as u64;
+        self.earnings = (self.earnings as u128 * remaining / total) as u64;
+        self.losses = (self.losses as u128 * remaining / total) as u64;
+        self.total_shares = remaining as u64;
+        Ok(amount)
+    }
+}
+
+impl PoolTranches {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // lending_pool
+                          Tranche::LEN + // senior
+                          Tranche::LEN + // junior
+                          8 + // senior_yield_cap
+                          8 + // last_distribution
+                          1; // bump
+
+    pub fn tranche(&self, kind: TrancheKind) -> &Tranche {
+        match kind {
+            TrancheKind::Senior => &self.senior,
+            TrancheKind::Junior => &self.junior,
+        }
+    }
+
+    pub fn tranche_mut(&mut self, kind: TrancheKind) -> &mut Tranche {
+        match kind {
+            TrancheKind::Senior => &mut self.senior,
+            TrancheKind::Junior => &mut self.junior,
+        }
+    }
+
+    /// Interest waterfall: senior up to its capped yield, junior takes the rest
+    pub fn distribute_interest(&mut self, interest: u64, now: i64) -> Result<()> {
+        let senior_cap = Mortgage::interest_for(
+            self.senior.value(),
+            self.senior_yield_cap,
+            now - self.last_distribution,
+        )?;
+        let senior_share = interest.min(senior_cap);
+
+        self.senior.earnings = self.senior.earnings
+            .checked_add(senior_share)
+            .ok_or(ErrorCode::Overflow)?;
+        self.junior.earnings = self.junior.earnings
+            .checked_add(interest - senior_share)
+            .ok_or(ErrorCode::Overflow)?;
+        self.last_distribution = now;
+        Ok(())
+    }
+
+    /// Loss waterfall: junior absorbs losses first, senior only once junior is wiped out
+    pub fn absorb_loss(&mut self, loss: u64) -> Result<()> {
+        let junior_loss = loss.min(self.junior.value());
+        let senior_loss = (loss - junior_loss).min(self.senior.value());
+
+        self.junior.losses = self.junior.losses
+            .checked_add(junior_loss)
+            .ok_or(ErrorCode::Overflow)?;
+        self.senior.losses = self.senior.losses
+            .checked_add(senior_loss)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+/// Lender's shares in one tranche of a pool
+#[account]
+#[derive(Default)]
+pub struct TranchePosition {
+    /// Owner of the position
+    pub owner: Pubkey,
+
+    /// Tranche accounting the position belongs to
+    pub pool_tranches: Pubkey,
+
+    /// True for the senior tranche, false for junior
+    pub is_senior: bool,
+
+    /// Shares held
+    pub shares: u64,
+
+    /// Last update timestamp
+    pub last_update_timestamp: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TranchePosition {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // owner
+                          32 + // pool_tranches
+                          1 + // is_senior
+                          8 + // shares
+                          8 + // last_update_timestamp
+                          1; // bump
+
+    pub fn kind(&self) -> TrancheKind {
+        if self.is_senior {
+            TrancheKind::Senior
+        } else {
+            TrancheKind::Junior
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeTranches<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = lending_pool.authority == authority.key() @ ErrorCode::Unauthorized)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PoolTranches::LEN,
+        seeds = [b"pool_tranches", lending_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_tranches: Account<'info, PoolTranches>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(tranche: TrancheKind)]
+pub struct DepositToTranche<'info> {
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    #[account(mut, constraint = lending_pool.is_active @ ErrorCode::PoolInactive)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool allowlist config PDA; uninitialized for open pools
+    #[account(seeds = [b"pool_access", lending_pool.key().as_ref()], bump)]
+    pub pool_access: UncheckedAccount<'info>,
+
+    /// CHECK: caller's pool attestation, validated by `enforce_pool_access`
+    pub pool_attestation: UncheckedAccount<'info>,
+
+    /// CHECK: pool limits PDA, verified by `enforce_deposit_limits`; uninitialized for uncapped pools
+    pub pool_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_tranches", lending_pool.key().as_ref()],
+        bump = pool_tranches.bump,
+    )]
+    pub pool_tranches: Account<'info, PoolTranches>,
+
+    #[account(
+        init_if_needed,
+        payer = lender,
+        space = TranchePosition::LEN,
+        seeds = [
+            b"tranche_position",
+            pool_tranches.key().as_ref(),
+            lender.key().as_ref(),
+            &[(tranche == TrancheKind::Senior) as u8],
+        ],
+        bump,
+    )]
+    pub tranche_position: Account<'info, TranchePosition>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.owner == lender.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromTranche<'info> {
+    pub lender: Signer<'info>,
+
+    #[account(mut)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    /// CHECK: pool withdrawal queue PDA; uninitialized for pools without a queue
+    pub withdrawal_queue: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_tranches", lending_pool.key().as_ref()],
+        bump = pool_tranches.bump,
+    )]
+    pub pool_tranches: Account<'info, PoolTranches>,
+
+    #[account(
+        mut,
+        constraint = tranche_position.owner == lender.key() @ ErrorCode::Unauthorized,
+        constraint = tranche_position.pool_tranches == pool_tranches.key() @ ErrorCode::InvalidParameter,
+    )]
+    pub tranche_position: Account<'info, TranchePosition>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.owner == lender.key() @ ErrorCode::Unauthorized,
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_tranches(ctx: Context<InitializeTranches>, senior_yield_cap: u64) -> Result<()> {
+    require!(senior_yield_cap <= 10_000, ErrorCode::InvalidInterestRate);
+
+    let pool_tranches = &mut ctx.accounts.pool_tranches;
+    pool_tranches.lending_pool = ctx.accounts.lending_pool.key();
+    pool_tranches.senior = Tranche::default();
+    pool_tranches.junior = Tranche::default();
+    pool_tranches.senior_yield_cap = senior_yield_cap;
+    pool_tranches.last_distribution = Clock::get()?.unix_timestamp;
+    pool_tranches.bump = ctx.bumps.pool_tranches;
+
+    Ok(())
+}
+
+pub fn deposit_to_tranche(
+    ctx: Context<DepositToTranche>,
+    tranche: TrancheKind,
+    amount: u64,
+) -> Result<()> {
+    enforce_pool_access(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.pool_access,
+        &ctx.accounts.pool_attestation,
+        &ctx.accounts.lender.key(),
+        PoolAccess::Deposit,
+    )?;
+
+    require!(amount > 0, ErrorCode::InvalidParameter);
+
+    // The per-lender cap applies to the current value of the lender's position
+    let held = match ctx.accounts.tranche_position.shares {
+        0 => 0,
+        shares => ctx.accounts.pool_tranches.tranche(tranche).amount_for(shares)?,
+    };
+    enforce_deposit_limits(
+        &ctx.accounts.lending_pool,
+        &ctx.accounts.pool_limits,
+        held,
+        amount,
+    )?;
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let pool_tranches = &mut ctx.accounts.pool_tranches;
+    let tranche_position = &mut ctx.accounts.tranche_position;
+    let clock = Clock::get()?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.lender_token_account.to_account_info(),
+                to: ctx.accounts.stablecoin_vault.to_account_info(),
+                authority: ctx.accounts.lender.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let pool_tranches_key = pool_tranches.key();
+    let state = pool_tranches.tranche_mut(tranche);
+    let shares = state.shares_for(amount)?;
+    state.principal = state.principal.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    state.total_shares = state.total_shares.checked_add(shares).ok_or(ErrorCode::Overflow)?;
+
+    tranche_position.owner = ctx.accounts.lender.key();
+    tranche_position.pool_tranches = pool_tranches_key;
+    tranche_position.is_senior = tranche == TrancheKind::Senior;
+    tranche_position.shares = tranche_position.shares
+        .checked_add(shares)
+        .ok_or(ErrorCode::Overflow)?;
+    tranche_position.last_update_timestamp = clock.unix_timestamp;
+    tranche_position.bump = ctx.bumps.tranche_position;
+
+    lending_pool.total_deposited = lending_pool.total_deposited
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn withdraw_from_tranche(ctx: Context<WithdrawFromTranche>, shares: u64) -> Result<()> {
+    require_withdrawal_queue_empty(
+        &ctx.accounts.lending_pool.key(),
+        &ctx.accounts.withdrawal_queue,
+    )?;
+
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let pool_tranches = &mut ctx.accounts.pool_tranches;
+    let tranche_position = &mut ctx.accounts.tranche_position;
+    let clock = Clock::get()?;
+
+    require!(
+        shares > 0 && shares <= tranche_position.shares,
+        ErrorCode::InvalidParameter
+    );
+
+    let state = pool_tranches.tranche_mut(tranche_position.kind());
+    require!(
+        lending_pool.available_liquidity() >= state.amount_for(shares)?,
+        ErrorCode::InsufficientLiquidity
+    );
+    let amount = state.redeem(shares)?;
+    tranche_position.shares -= shares;
+    tranche_position.last_update_timestamp = clock.unix_timestamp;
+
+    let seeds = &[
+        b"lending_pool".as_ref(),
+        lending_pool.authority.as_ref(),
+        &[lending_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.stablecoin_vault.to_account_info(),
+                to: ctx.accounts.lender_token_account.to_account_info(),
+                authority: lending_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(amount);
+    lending_pool.last_updated = clock.unix_timestamp;
+
+    Ok(())
+}
+
+/// Runs repayment interest and realized losses through the pool's tranche waterfall.
+/// Pools without tranches pass the uninitialized `["pool_tranches", pool]` PDA.
+pub fn apply_tranche_waterfall(
+    lending_pool: &Pubkey,
+    pool_tranches: &AccountInfo,
+    interest: u64,
+    loss: u64,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"pool_tranches", lending_pool.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(pool_tranches.key(), expected, ErrorCode::InvalidParameter);
+    if pool_tranches.data_is_empty() || (interest == 0 && loss == 0) {
+        return Ok(());
+    }
+
+    let mut tranches = Account::<PoolTranches>::try_from(pool_tranches)?;
+    if interest > 0 {
+        tranches.distribute_interest(interest, Clock::get()?.unix_timestamp)?;
+    }
+    if loss > 0 {
+        tranches.absorb_loss(loss)?;
+    }
+    tranches.exit(&crate::ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_for_first_deposit_is_one_to_one() {
+        let tranche = Tranche::default();
+        assert_eq!(tranche.shares_for(1_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn shares_for_prices_at_tranche_value() {
+        let tranche = Tranche {
+            principal: 1_000,
+            earnings: 200,
+            losses: 200,
+            total_shares: 500,
+        };
+        assert_eq!(tranche.shares_for(200).unwrap(), 100);
+    }
+
+    #[test]
+    fn shares_for_rejects_written_down_tranche() {
+        let tranche = Tranche {
+            principal: 100,
+            earnings: 0,
+            losses: 100,
+            total_shares: 50,
+        };
+        assert_eq!(
+            tranche.shares_for(100).unwrap_err(),
+            ErrorCode::TrancheWrittenDown.into()
+        );
+    }
+}
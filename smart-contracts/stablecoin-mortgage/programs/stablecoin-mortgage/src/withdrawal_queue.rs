@@ -0,0 +1,385 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::tranches::{PoolTranches, TrancheKind, TranchePosition};
+
+/// FIFO queue of withdrawals waiting for pool liquidity
+#[account]
+#[derive(Default)]
+pub struct WithdrawalQueue {
+    /// Lending pool the queue belongs to
+    pub lending_pool: Pubkey,
+
+    /// Ticket of the request at the front of the queue
+    pub head: u64,
+
+    /// Ticket assigned to the next request
+    pub tail: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl WithdrawalQueue {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // lending_pool
+                          8 + // head
+                          8 + // tail
+                          1; // bump
+
+    pub fn is_empty(&self) -> bool {
+        self.head == self.tail
+    }
+}
+
+/// Queued claim on pool liquidity, held as tranche shares until it is filled.
+/// The shares keep sharing the tranche's earnings and losses while they wait.
+#[account]
+#[derive(Default)]
+pub struct WithdrawalRequest {
+    /// Lender who queued the withdrawal
+    pub owner: Pubkey,
+
+    /// Queue holding the request
+    pub queue: Pubkey,
+
+    /// Position in the queue
+    pub ticket: u64,
+
+    /// Tranche position the shares were taken from
+    pub tranche_position: Pubkey,
+
+    /// True for senior tranche shares, false for junior
+    pub is_senior: bool,
+
+    /// Tranche shares queued for redemption
+    pub shares_requested: u64,
+
+    /// Shares redeemed so far
+    pub shares_redeemed: u64,
+
+    /// Amount paid out so far
+    pub amount_paid: u64,
+
+    /// True if the lender cancelled the remainder
+    pub is_cancelled: bool,
+
+    /// Request date
+    pub request_date: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl WithdrawalRequest {
+    pub const LEN: usize = 8 + // discriminator
+                          32 + // owner
+                          32 + // queue
+                          8 + // ticket
+                          32 + // tranche_position
+                          1 + // is_senior
+                          8 + // shares_requested
+                          8 + // shares_redeemed
+                          8 + // amount_paid
+                          1 + // is_cancelled
+                          8 + // request_date
+                          1; // bump
+
+    pub fn remaining_shares(&self) -> u64 {
+        self.shares_requested.saturating_sub(self.shares_redeemed)
+    }
+
+    pub fn kind(&self) -> TrancheKind {
+        if self.is_senior {
+            TrancheKind::Senior
+        } else {
+            TrancheKind::Junior
+        }
+    }
+}
+
+/// Fails while the pool has queued withdrawals, so direct withdrawals can't jump the queue.
+/// Pools without a queue pass the uninitialized `["withdrawal_queue", pool]` PDA.
+pub fn require_withdrawal_queue_empty(
+    lending_pool: &Pubkey,
+    withdrawal_queue: &AccountInfo,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"withdrawal_queue", lending_pool.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(withdrawal_queue.key(), expected, ErrorCode::InvalidParameter);
+    if withdrawal_queue.data_is_empty() {
+        return Ok(());
+    }
+    let queue = Account::<WithdrawalQueue>::try_from(withdrawal_queue)?;
+    require!(queue.is_empty(), ErrorCode::WithdrawalQueueNotEmpty);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeWithdrawalQueue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = WithdrawalQueue::LEN,
+        seeds = [b"withdrawal_queue", lending_pool.key().as_ref()],
+        bump,
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        seeds = [b"pool_tranches", lending_pool.key().as_ref()],
+        bump = pool_tranches.bump,
+    )]
+    pub pool_tranches: Account<'info, PoolTranches>,
+
+    #[account(
+        mut,
+        constraint = tranche_position.owner == lender.key() @ ErrorCode::Unauthorized,
+        constraint = tranche_position.pool_tranches == pool_tranches.key() @ ErrorCode::InvalidParameter,
+    )]
+    pub tranche_position: Account<'info, TranchePosition>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal_queue", lending_pool.key().as_ref()],
+        bump = withdrawal_queue.bump,
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    #[account(
+        init,
+        payer = lender,
+        space = WithdrawalRequest::LEN,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_queue.key().as_ref(),
+            &withdrawal_queue.tail.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessWithdrawalQueue<'info> {
+    #[account(mut)]
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_tranches", lending_pool.key().as_ref()],
+        bump = pool_tranches.bump,
+    )]
+    pub pool_tranches: Account<'info, PoolTranches>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal_queue", lending_pool.key().as_ref()],
+        bump = withdrawal_queue.bump,
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_queue.key().as_ref(),
+            &withdrawal_queue.head.to_le_bytes(),
+        ],
+        bump = withdrawal_request.bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    /// CHECK: request owner, receives the request rent once it is closed
+    #[account(mut, constraint = owner.key() == withdrawal_request.owner @ ErrorCode::Unauthorized)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = stablecoin_vault.key() == lending_pool.stablecoin_vault @ ErrorCode::InvalidParameter,
+    )]
+    pub stablecoin_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == withdrawal_request.owner @ ErrorCode::Unauthorized,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWithdrawalRequest<'info> {
+    pub lender: Signer<'info>,
+
+    pub lending_pool: Account<'info, LendingPool>,
+
+    #[account(
+        mut,
+        constraint = tranche_position.key() == withdrawal_request.tranche_position @ ErrorCode::InvalidParameter,
+    )]
+    pub tranche_position: Account<'info, TranchePosition>,
+
+    #[account(
+        seeds = [b"withdrawal_queue", lending_pool.key().as_ref()],
+        bump = withdrawal_queue.bump,
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    #[account(
+        mut,
+        constraint = withdrawal_request.owner == lender.key() @ ErrorCode::Unauthorized,
+        constraint = withdrawal_request.queue == withdrawal_queue.key() @ ErrorCode::InvalidParameter,
+        constraint = !withdrawal_request.is_cancelled @ ErrorCode::WithdrawalRequestClosed,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+}
+
+pub fn initialize_withdrawal_queue(ctx: Context<InitializeWithdrawalQueue>) -> Result<()> {
+    let withdrawal_queue = &mut ctx.accounts.withdrawal_queue;
+    withdrawal_queue.lending_pool = ctx.accounts.lending_pool.key();
+    withdrawal_queue.head = 0;
+    withdrawal_queue.tail = 0;
+    withdrawal_queue.bump = ctx.bumps.withdrawal_queue;
+    Ok(())
+}
+
+pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
+    let tranche_position = &mut ctx.accounts.tranche_position;
+    let withdrawal_queue = &mut ctx.accounts.withdrawal_queue;
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let clock = Clock::get()?;
+
+    require!(
+        shares > 0 && shares <= tranche_position.shares,
+        ErrorCode::InvalidParameter
+    );
+
+    // Only withdrawals that can't be served now are queued; FIFO means an
+    // existing queue always goes first
+    let value = ctx.accounts.pool_tranches.tranche(tranche_position.kind()).amount_for(shares)?;
+    require!(
+        !withdrawal_queue.is_empty() || ctx.accounts.lending_pool.available_liquidity() < value,
+        ErrorCode::WithdrawalNotQueueable
+    );
+
+    // The shares leave the position immediately so they can't be withdrawn twice
+    tranche_position.shares -= shares;
+    tranche_position.last_update_timestamp = clock.unix_timestamp;
+
+    withdrawal_request.owner = ctx.accounts.lender.key();
+    withdrawal_request.queue = withdrawal_queue.key();
+    withdrawal_request.ticket = withdrawal_queue.tail;
+    withdrawal_request.tranche_position = tranche_position.key();
+    withdrawal_request.is_senior = tranche_position.is_senior;
+    withdrawal_request.shares_requested = shares;
+    withdrawal_request.shares_redeemed = 0;
+    withdrawal_request.amount_paid = 0;
+    withdrawal_request.is_cancelled = false;
+    withdrawal_request.request_date = clock.unix_timestamp;
+    withdrawal_request.bump = ctx.bumps.withdrawal_request;
+
+    withdrawal_queue.tail += 1;
+
+    Ok(())
+}
+
+pub fn process_withdrawal_queue(ctx: Context<ProcessWithdrawalQueue>) -> Result<()> {
+    let lending_pool = &mut ctx.accounts.lending_pool;
+    let withdrawal_queue = &mut ctx.accounts.withdrawal_queue;
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let clock = Clock::get()?;
+
+    if !withdrawal_request.is_cancelled {
+        // Shares are priced now, so queued lenders share gains and losses until filled
+        let tranche = ctx.accounts.pool_tranches.tranche_mut(withdrawal_request.kind());
+        let remaining = withdrawal_request.remaining_shares();
+        let value = tranche.amount_for(remaining)?;
+        let liquidity = lending_pool.available_liquidity();
+        let shares = if value <= liquidity {
+            remaining
+        } else {
+            u64::try_from(remaining as u128 * liquidity as u128 / value as u128)
+                .map_err(|_| ErrorCode::Overflow)?
+        };
+        require!(shares > 0, ErrorCode::InsufficientLiquidity);
+        let fill = tranche.redeem(shares)?;
+
+        let seeds = &[
+            b"lending_pool".as_ref(),
+            lending_pool.authority.as_ref(),
+            &[lending_pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        if fill > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.stablecoin_vault.to_account_info(),
+                        to: ctx.accounts.owner_token_account.to_account_info(),
+                        authority: lending_pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                fill,
+            )?;
+        }
+
+        withdrawal_request.shares_redeemed += shares;
+        withdrawal_request.amount_paid = withdrawal_request.amount_paid
+            .checked_add(fill)
+            .ok_or(ErrorCode::Overflow)?;
+        lending_pool.total_deposited = lending_pool.total_deposited.saturating_sub(fill);
+        lending_pool.last_updated = clock.unix_timestamp;
+    }
+
+    // Filled or cancelled requests leave the queue and return their rent
+    if withdrawal_request.is_cancelled || withdrawal_request.remaining_shares() == 0 {
+        withdrawal_queue.head += 1;
+        withdrawal_request.close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+pub fn cancel_withdrawal_request(ctx: Context<CancelWithdrawalRequest>) -> Result<()> {
+    let tranche_position = &mut ctx.accounts.tranche_position;
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+
+    // The unredeemed shares go back into the lender's position;
+    // the crank skips the request when it reaches the front of the queue
+    tranche_position.shares = tranche_position.shares
+        .checked_add(withdrawal_request.remaining_shares())
+        .ok_or(ErrorCode::Overflow)?;
+    tranche_position.last_update_timestamp = Clock::get()?.unix_timestamp;
+
+    withdrawal_request.is_cancelled = true;
+
+    Ok(())
+}